@@ -55,7 +55,11 @@ function isAllowedMobileRedirect(redirectUrl: string | undefined): boolean {
   if (!redirectUrl) return false;
   const lower = redirectUrl.toLowerCase();
   if (lower.startsWith('http://') || lower.startsWith('https://')) return false;
-  return lower.startsWith('gruenerator://') || lower.startsWith('gruenerator-docs://');
+  return (
+    lower.startsWith('gruenerator://') ||
+    lower.startsWith('gruenerator-staging://') ||
+    lower.startsWith('gruenerator-docs://')
+  );
 }
 
 function appendQueryParam(url: string, key: string, value: string): string {
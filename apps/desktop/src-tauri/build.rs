@@ -1,3 +1,15 @@
+use std::path::Path;
+
+const LICENSES_FILE: &str = "licenses/THIRD_PARTY_LICENSES.json";
+
 fn main() {
+    // Generated by `pnpm licenses` in release builds. Local builds without it still compile
+    // and `get_licenses` reports an empty list.
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    let bundled = Path::new(&out_dir).join("third_party_licenses.json");
+    let content = std::fs::read_to_string(LICENSES_FILE).unwrap_or_default();
+    std::fs::write(bundled, content).expect("failed to write bundled licenses");
+    println!("cargo:rerun-if-changed=licenses");
+
     tauri_build::build()
 }
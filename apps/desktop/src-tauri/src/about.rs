@@ -0,0 +1,106 @@
+//! Native "Über Grünerator" panel. The `about` menu item still emits `menu-about` for the
+//! in-app dialog; this is for users who expect the platform's own panel.
+
+use chrono::{Datelike, Utc};
+
+const LICENSES_URL: &str = "https://github.com/netzbegruenung/Gruenerator/blob/main/LICENSE.md";
+
+struct AboutInfo {
+    name: String,
+    version: String,
+    copyright: String,
+}
+
+impl AboutInfo {
+    fn new(app: &tauri::AppHandle) -> Self {
+        let package = app.package_info();
+        let copyright = app
+            .config()
+            .bundle
+            .copyright
+            .clone()
+            .unwrap_or_else(|| format!("© {} {}", Utc::now().year(), package.authors));
+        Self {
+            name: package.name.clone(),
+            version: package.version.to_string(),
+            copyright,
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn show(app: &tauri::AppHandle, about: AboutInfo) -> Result<(), String> {
+    use objc2::rc::Retained;
+    use objc2::runtime::AnyObject;
+    use objc2::MainThreadMarker;
+    use objc2_app_kit::{
+        NSAboutPanelOptionApplicationName, NSAboutPanelOptionApplicationVersion,
+        NSAboutPanelOptionCredits, NSApplication, NSLinkAttributeName,
+    };
+    use objc2_foundation::{ns_string, NSAttributedString, NSDictionary, NSString, NSURL};
+
+    app.run_on_main_thread(move || {
+        let Some(mtm) = MainThreadMarker::new() else {
+            return;
+        };
+
+        let name = NSString::from_str(&about.name);
+        let version = NSString::from_str(&about.version);
+        let copyright = NSString::from_str(&about.copyright);
+        let credits: Retained<NSAttributedString> =
+            match NSURL::URLWithString(&NSString::from_str(LICENSES_URL)) {
+                Some(url) => {
+                    let url: &AnyObject = &url;
+                    let attributes = NSDictionary::from_slices(&[unsafe { NSLinkAttributeName }], &[url]);
+                    unsafe {
+                        NSAttributedString::new_with_attributes(ns_string!("Open-Source-Lizenzen"), &attributes)
+                    }
+                }
+                None => NSAttributedString::from_nsstring(ns_string!("Open-Source-Lizenzen")),
+            };
+
+        let keys = unsafe {
+            [
+                NSAboutPanelOptionApplicationName,
+                NSAboutPanelOptionApplicationVersion,
+                ns_string!("Copyright"),
+                NSAboutPanelOptionCredits,
+            ]
+        };
+        let values: [&AnyObject; 4] = [&name, &version, &copyright, &credits];
+        let options = NSDictionary::from_slices(&keys, &values);
+
+        let app = NSApplication::sharedApplication(mtm);
+        unsafe { app.orderFrontStandardAboutPanelWithOptions(&options) };
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn show(app: &tauri::AppHandle, about: AboutInfo) -> Result<(), String> {
+    use tauri_plugin_dialog::{DialogExt, MessageDialogButtons};
+    use tauri_plugin_opener::OpenerExt;
+
+    let handle = app.clone();
+    app.dialog()
+        .message(format!("Version {}\n\n{}", about.version, about.copyright))
+        .title(format!("Über {}", about.name))
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Open-Source-Lizenzen".to_string(),
+            "Schließen".to_string(),
+        ))
+        .show(move |open_licenses| {
+            if open_licenses {
+                if let Err(e) = handle.opener().open_url(LICENSES_URL, None::<&str>) {
+                    log::error!("Failed to open licenses: {}", e);
+                }
+            }
+        });
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn show_about_dialog(app: tauri::AppHandle) -> Result<(), String> {
+    let about = AboutInfo::new(&app);
+    show(&app, about)
+}
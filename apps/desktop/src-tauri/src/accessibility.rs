@@ -0,0 +1,137 @@
+//! Debug dumps for screen-reader QA of the native chrome: what assistive technology sees of a
+//! window (role and name per element), and the menu structure it is built from. Available in
+//! debug builds or with the `accessibility_debug` feature flag.
+//!
+//! Only Windows is queried, through UI Automation. macOS requires the accessibility permission
+//! even for introspecting the own process, and Linux would need an AT-SPI client, so both
+//! report `unsupported` for the tree; the menu dump works everywhere.
+
+use serde::Serialize;
+use tauri::menu::MenuItemKind;
+use tauri::Wry;
+
+use crate::features;
+
+pub const FEATURE_FLAG: &str = "accessibility_debug";
+#[cfg(windows)]
+const MAX_DEPTH: usize = 12;
+/// The webview contributes the whole page; stop well before the dump gets unwieldy.
+#[cfg(windows)]
+const MAX_NODES: usize = 2000;
+
+#[derive(Clone, Serialize)]
+pub struct AccessibleNode {
+    role: String,
+    name: String,
+    children: Vec<AccessibleNode>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct MenuNode {
+    id: String,
+    kind: &'static str,
+    text: String,
+    enabled: bool,
+    children: Vec<MenuNode>,
+}
+
+fn ensure_enabled(app: &tauri::AppHandle) -> Result<(), String> {
+    if cfg!(debug_assertions) || features::is_enabled(app, FEATURE_FLAG) {
+        Ok(())
+    } else {
+        Err("debug_disabled".to_string())
+    }
+}
+
+#[cfg(windows)]
+fn query_tree(hwnd: isize) -> Result<AccessibleNode, String> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED};
+    use windows::Win32::UI::Accessibility::{CUIAutomation, IUIAutomation, IUIAutomationElement, IUIAutomationTreeWalker};
+
+    fn node(walker: &IUIAutomationTreeWalker, element: &IUIAutomationElement, depth: usize, budget: &mut usize) -> AccessibleNode {
+        let (role, name) = unsafe {
+            (
+                element.CurrentLocalizedControlType().map(|role| role.to_string()).unwrap_or_default(),
+                element.CurrentName().map(|name| name.to_string()).unwrap_or_default(),
+            )
+        };
+        let mut children = Vec::new();
+        if depth < MAX_DEPTH {
+            let mut child = unsafe { walker.GetFirstChildElement(element) }.ok();
+            while let Some(current) = child {
+                if *budget == 0 {
+                    break;
+                }
+                *budget -= 1;
+                children.push(node(walker, &current, depth + 1, budget));
+                child = unsafe { walker.GetNextSiblingElement(&current) }.ok();
+            }
+        }
+        AccessibleNode { role, name, children }
+    }
+
+    unsafe {
+        // UI Automation must not be called from the thread owning the window, so this runs on
+        // a blocking worker that gets its own apartment.
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        let automation: IUIAutomation =
+            CoCreateInstance(&CUIAutomation, None, CLSCTX_INPROC_SERVER).map_err(|e| e.to_string())?;
+        let root = automation
+            .ElementFromHandle(HWND(hwnd as *mut _))
+            .map_err(|e| e.to_string())?;
+        let walker = automation.ControlViewWalker().map_err(|e| e.to_string())?;
+        let mut budget = MAX_NODES;
+        Ok(node(&walker, &root, 0, &mut budget))
+    }
+}
+
+/// The accessible role/name tree of the calling window, including its menu bar.
+#[tauri::command]
+pub async fn dump_accessibility_labels(app: tauri::AppHandle, window: tauri::Window) -> Result<AccessibleNode, String> {
+    ensure_enabled(&app)?;
+    #[cfg(windows)]
+    {
+        let hwnd = window.hwnd().map_err(|e| e.to_string())?.0 as isize;
+        tauri::async_runtime::spawn_blocking(move || query_tree(hwnd))
+            .await
+            .map_err(|e| e.to_string())?
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = window;
+        Err("unsupported".to_string())
+    }
+}
+
+fn menu_node(item: &MenuItemKind<Wry>) -> tauri::Result<MenuNode> {
+    let (kind, text, enabled, children) = match item {
+        MenuItemKind::MenuItem(item) => ("item", item.text()?, item.is_enabled()?, Vec::new()),
+        MenuItemKind::Check(item) => ("check", item.text()?, item.is_enabled()?, Vec::new()),
+        MenuItemKind::Icon(item) => ("icon", item.text()?, item.is_enabled()?, Vec::new()),
+        MenuItemKind::Predefined(item) => ("predefined", item.text()?, true, Vec::new()),
+        MenuItemKind::Submenu(submenu) => {
+            let children = submenu.items()?.iter().map(menu_node).collect::<tauri::Result<_>>()?;
+            ("submenu", submenu.text()?, submenu.is_enabled()?, children)
+        }
+    };
+    Ok(MenuNode {
+        id: item.id().0.clone(),
+        kind,
+        text,
+        enabled,
+        children,
+    })
+}
+
+/// The calling window's menu as built, for comparing against what a screen reader announces.
+#[tauri::command]
+pub async fn dump_menu_structure(app: tauri::AppHandle, window: tauri::Window) -> Result<Vec<MenuNode>, String> {
+    ensure_enabled(&app)?;
+    let Some(menu) = window.menu() else {
+        return Ok(Vec::new());
+    };
+    menu.items()
+        .and_then(|items| items.iter().map(menu_node).collect())
+        .map_err(|e| e.to_string())
+}
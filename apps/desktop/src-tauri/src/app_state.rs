@@ -0,0 +1,101 @@
+//! One snapshot of everything the frontend otherwise tracks through separate events (theme,
+//! do-not-disturb, density, tasks, idle reset, load errors). Modules that emit one of those
+//! granular events call [`refresh`] afterwards; whenever the snapshot differs from the last one
+//! its `version` increases and `app-state-changed` carries the new snapshot, so a listener
+//! that sees a gap in versions knows to re-query `get_app_state`.
+//!
+//! The granular events stay for existing listeners.
+
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{Emitter, Manager, Theme};
+
+use crate::{auto_reset, dnd, paths, safe_mode, tasks, touch, webview, window};
+
+#[derive(Clone, Default, PartialEq, Serialize)]
+struct Fields {
+    theme: String,
+    scale_factor: f64,
+    do_not_disturb: bool,
+    ui_density: String,
+    touch_mode: bool,
+    active_tasks: usize,
+    generating: bool,
+    /// The idle auto-reset countdown is running.
+    auto_reset_pending: bool,
+    load_failed: bool,
+    safe_mode: bool,
+    portable: bool,
+}
+
+#[derive(Clone, Serialize)]
+pub struct AppStateSnapshot {
+    version: u64,
+    #[serde(flatten)]
+    fields: Fields,
+}
+
+#[derive(Default)]
+pub struct AppState(Mutex<Option<AppStateSnapshot>>);
+
+fn collect(app: &tauri::AppHandle) -> Fields {
+    let main_window = app.get_webview_window("main");
+    Fields {
+        theme: match main_window.as_ref().and_then(|window| window.theme().ok()) {
+            Some(Theme::Dark) => "dark",
+            _ => "light",
+        }
+        .to_string(),
+        scale_factor: main_window
+            .as_ref()
+            .and_then(|window| window.scale_factor().ok())
+            .unwrap_or(1.0),
+        do_not_disturb: dnd::is_enabled(app),
+        ui_density: window::ui_density(app),
+        touch_mode: touch::is_enabled(app),
+        active_tasks: app
+            .try_state::<tasks::TaskRegistry>()
+            .map(|registry| registry.list().len())
+            .unwrap_or(0),
+        generating: auto_reset::is_generating(app),
+        auto_reset_pending: auto_reset::is_counting_down(app),
+        load_failed: webview::load_failed(app),
+        safe_mode: safe_mode::is_active(app),
+        portable: app.state::<paths::Portable>().is_active(),
+    }
+}
+
+/// The current snapshot, and whether it differs from the previous one.
+fn snapshot(app: &tauri::AppHandle) -> (AppStateSnapshot, bool) {
+    let fields = collect(app);
+    let state = app.state::<AppState>();
+    let mut current = state.0.lock().unwrap();
+    match current.as_ref() {
+        Some(snapshot) if snapshot.fields == fields => (snapshot.clone(), false),
+        previous => {
+            let snapshot = AppStateSnapshot {
+                version: previous.map(|snapshot| snapshot.version + 1).unwrap_or(1),
+                fields,
+            };
+            *current = Some(snapshot.clone());
+            (snapshot, true)
+        }
+    }
+}
+
+/// Re-reads the tracked fields and emits `app-state-changed` if any of them changed.
+pub fn refresh(app: &tauri::AppHandle) {
+    if app.try_state::<AppState>().is_none() {
+        return;
+    }
+    let (snapshot, changed) = snapshot(app);
+    if changed {
+        let _ = app.emit("app-state-changed", snapshot);
+    }
+}
+
+#[tauri::command]
+pub async fn get_app_state(app: tauri::AppHandle) -> Result<AppStateSnapshot, String> {
+    Ok(snapshot(&app).0)
+}
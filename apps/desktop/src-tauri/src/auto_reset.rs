@@ -0,0 +1,125 @@
+//! Idle auto-reset for unattended booth installs: after `idle_minutes` without user activity
+//! the main window is reloaded so the next visitor starts fresh. The frontend reports
+//! activity via `report_activity`; a pending generation or background task counts as activity.
+
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde_json::json;
+use tauri::{Emitter, Manager};
+
+use crate::{app_state, now_millis, settings, tasks};
+
+const IDLE_MINUTES_KEY: &str = "auto_reset_idle_minutes";
+const COUNTDOWN_SECS: u64 = 10;
+
+#[derive(Default)]
+pub struct AutoReset {
+    idle_minutes: AtomicU32,
+    last_activity: AtomicU64,
+    generating: AtomicBool,
+    counting_down: AtomicBool,
+}
+
+impl AutoReset {
+    fn touch(&self, app: &tauri::AppHandle) {
+        self.last_activity.store(now_millis(), Ordering::SeqCst);
+        if self.counting_down.swap(false, Ordering::SeqCst) {
+            let _ = app.emit("auto-reset-cancelled", ());
+            app_state::refresh(app);
+        }
+    }
+
+    fn is_busy(&self, app: &tauri::AppHandle) -> bool {
+        self.generating.load(Ordering::SeqCst) || !app.state::<tasks::TaskRegistry>().list().is_empty()
+    }
+}
+
+pub fn init(app: &tauri::AppHandle) {
+    let state = AutoReset::default();
+    state
+        .idle_minutes
+        .store(settings::get(app, IDLE_MINUTES_KEY).unwrap_or(0), Ordering::SeqCst);
+    state.last_activity.store(now_millis(), Ordering::SeqCst);
+    app.manage(state);
+
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_secs(1));
+        tick(&app);
+    });
+}
+
+pub fn is_generating(app: &tauri::AppHandle) -> bool {
+    app.try_state::<AutoReset>()
+        .map(|state| state.generating.load(Ordering::SeqCst))
+        .unwrap_or(false)
+}
+
+pub fn is_counting_down(app: &tauri::AppHandle) -> bool {
+    app.try_state::<AutoReset>()
+        .map(|state| state.counting_down.load(Ordering::SeqCst))
+        .unwrap_or(false)
+}
+
+fn tick(app: &tauri::AppHandle) {
+    let state = app.state::<AutoReset>();
+    let idle_minutes = state.idle_minutes.load(Ordering::SeqCst);
+    if idle_minutes == 0 {
+        return;
+    }
+    if state.is_busy(app) {
+        state.touch(app);
+        return;
+    }
+
+    let limit = u64::from(idle_minutes) * 60_000;
+    let idle = now_millis().saturating_sub(state.last_activity.load(Ordering::SeqCst));
+    if idle >= limit {
+        log::info!("No activity for {} min, resetting", idle_minutes);
+        state.counting_down.store(false, Ordering::SeqCst);
+        state.last_activity.store(now_millis(), Ordering::SeqCst);
+        if let Some(main_window) = app.get_webview_window("main") {
+            if let Err(e) = main_window.reload() {
+                log::error!("Auto-reset failed: {}", e);
+            }
+        }
+    } else if idle + COUNTDOWN_SECS * 1000 >= limit && !state.counting_down.swap(true, Ordering::SeqCst) {
+        let _ = app.emit(
+            "auto-reset-triggered",
+            json!({ "seconds": (limit - idle).div_ceil(1000) }),
+        );
+        app_state::refresh(app);
+    }
+}
+
+/// Enables the reset after `idle_minutes` without activity; `0` disables it.
+#[tauri::command]
+pub async fn set_auto_reset(app: tauri::AppHandle, idle_minutes: u32) -> Result<(), String> {
+    settings::set(&app, IDLE_MINUTES_KEY, idle_minutes)?;
+    let state = app.state::<AutoReset>();
+    state.idle_minutes.store(idle_minutes, Ordering::SeqCst);
+    state.touch(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_auto_reset(app: tauri::AppHandle) -> Result<u32, String> {
+    Ok(app.state::<AutoReset>().idle_minutes.load(Ordering::SeqCst))
+}
+
+#[tauri::command]
+pub async fn report_activity(app: tauri::AppHandle) -> Result<(), String> {
+    app.state::<AutoReset>().touch(&app);
+    Ok(())
+}
+
+/// Marks a text generation as running, which holds off the reset until it finishes.
+#[tauri::command]
+pub async fn set_generation_in_flight(app: tauri::AppHandle, active: bool) -> Result<(), String> {
+    let state = app.state::<AutoReset>();
+    state.generating.store(active, Ordering::SeqCst);
+    state.touch(&app);
+    app_state::refresh(&app);
+    Ok(())
+}
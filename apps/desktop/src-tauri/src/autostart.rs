@@ -0,0 +1,335 @@
+//! Inspects the login item the autostart plugin registered. The registration stores an
+//! absolute path, so it goes stale when an update moves the binary; re-enabling through the
+//! plugin rewrites it with the current executable. The arguments passed on login start are
+//! configurable, so registering goes through our own `AutoLaunch` with the saved arguments;
+//! the plugin's fixed ones would otherwise win.
+
+#[cfg(not(windows))]
+use std::path::PathBuf;
+
+use auto_launch::{AutoLaunch, AutoLaunchBuilder};
+use serde::Serialize;
+use serde_json::json;
+use tauri::Manager;
+use tauri_plugin_autostart::ManagerExt;
+
+use crate::{safe_mode, settings, startup};
+
+pub const MINIMIZED_ARG: &str = "--minimized";
+/// Marks a login launch without implying the tray. Always registered, whatever the user's
+/// arguments, so `was_autostarted` also works when the login item shows the window.
+pub const AUTOSTART_ARG: &str = "--autostart";
+/// Start in the tray on every launch, not only when autostart passes `MINIMIZED_ARG`.
+const START_MINIMIZED_KEY: &str = "start_minimized";
+const ARGS_KEY: &str = "autostart_args";
+/// Arguments the login item may pass; anything else could smuggle flags into the launch.
+const ALLOWED_ARGS: &[&str] = &[MINIMIZED_ARG, safe_mode::SAFE_MODE_ARG];
+
+/// How this launch was started, decided once in `setup`.
+struct LaunchFlags {
+    /// Started by the login item rather than the user.
+    autostarted: bool,
+    /// Keeps the main window hidden once it has loaded.
+    minimized: bool,
+}
+
+#[derive(Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AutostartScope {
+    /// Per-user login item (Run key, LaunchAgent, XDG autostart entry).
+    User,
+    /// System-wide LaunchDaemon on macOS.
+    System,
+}
+
+#[derive(Clone, Serialize)]
+pub struct AutostartDetails {
+    pub enabled: bool,
+    pub minimized: bool,
+    /// The arguments the next registration passes, see `set_autostart_args`.
+    pub args: Vec<String>,
+    pub scope: Option<AutostartScope>,
+    pub registered_path: Option<String>,
+    pub current_path: Option<String>,
+    /// The registration points somewhere other than the running executable.
+    pub stale: bool,
+}
+
+struct Registration {
+    scope: AutostartScope,
+    path: String,
+    minimized: bool,
+}
+
+/// The path the plugin would register for this executable.
+fn current_path(app: &tauri::AppHandle) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    if let Some(appimage) = app.env().appimage {
+        return Some(appimage.to_string_lossy().into_owned());
+    }
+    let _ = app;
+    let exe = std::env::current_exe().ok()?;
+    #[cfg(target_os = "macos")]
+    let exe = exe.canonicalize().ok()?;
+    Some(exe.display().to_string())
+}
+
+/// Splits a `"<path> <args…>"` command line as written by the Windows and Linux backends. The
+/// path may contain spaces, so only trailing arguments we register are split off.
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+fn parse_command_line(scope: AutostartScope, command: &str) -> Registration {
+    let mut path = command.trim();
+    let mut minimized = false;
+    while let Some(arg) = ALLOWED_ARGS
+        .iter()
+        .chain([&AUTOSTART_ARG])
+        .find(|arg| path.ends_with(*arg))
+    {
+        minimized |= *arg == MINIMIZED_ARG;
+        path = path[..path.len() - arg.len()].trim_end();
+    }
+    Registration { scope, path: path.to_string(), minimized }
+}
+
+/// The saved login arguments, `MINIMIZED_ARG` until the user changes them.
+fn saved_args(app: &tauri::AppHandle) -> Vec<String> {
+    let args: Vec<String> = settings::get(app, ARGS_KEY).unwrap_or_else(|| vec![MINIMIZED_ARG.to_string()]);
+    args.into_iter()
+        .filter(|arg| ALLOWED_ARGS.contains(&arg.as_str()))
+        .collect()
+}
+
+/// Registers the login item for the current executable with `AUTOSTART_ARG` and the saved
+/// arguments, replacing an existing one. Name and path match the plugin's, so it still sees
+/// the registration.
+pub fn enable(app: &tauri::AppHandle) -> Result<(), String> {
+    let path = current_path(app).ok_or_else(|| "current executable unavailable".to_string())?;
+    let mut args = vec![AUTOSTART_ARG.to_string()];
+    args.extend(saved_args(app));
+    let mut builder = AutoLaunchBuilder::new();
+    builder
+        .set_app_name(&app.package_info().name)
+        .set_app_path(&path)
+        .set_args(&args);
+    #[cfg(target_os = "macos")]
+    builder.set_use_launch_agent(true);
+    let launcher: AutoLaunch = builder.build().map_err(|e| e.to_string())?;
+    launcher.enable().map_err(|e| e.to_string())
+}
+
+#[cfg(windows)]
+fn registration(app: &tauri::AppHandle) -> Option<Registration> {
+    use winreg::enums::{HKEY_CURRENT_USER, KEY_READ};
+    use winreg::RegKey;
+
+    let command: String = RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey_with_flags("SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Run", KEY_READ)
+        .ok()?
+        .get_value(&app.package_info().name)
+        .ok()?;
+    Some(parse_command_line(AutostartScope::User, &command))
+}
+
+#[cfg(target_os = "macos")]
+fn registration(app: &tauri::AppHandle) -> Option<Registration> {
+    let file = format!("{}.plist", app.package_info().name);
+    let candidates = [
+        (
+            AutostartScope::User,
+            app.path().home_dir().ok()?.join("Library/LaunchAgents").join(&file),
+        ),
+        (AutostartScope::System, PathBuf::from("/Library/LaunchDaemons").join(&file)),
+    ];
+    candidates.into_iter().find_map(|(scope, path)| {
+        let plist = std::fs::read_to_string(path).ok()?;
+        let arguments = program_arguments(&plist);
+        Some(Registration {
+            scope,
+            path: arguments.first()?.clone(),
+            minimized: arguments.iter().any(|arg| arg == MINIMIZED_ARG),
+        })
+    })
+}
+
+/// The `<string>` entries of the `ProgramArguments` array in a launchd plist.
+#[cfg(target_os = "macos")]
+fn program_arguments(plist: &str) -> Vec<String> {
+    let Some(start) = plist.find("<key>ProgramArguments</key>") else {
+        return Vec::new();
+    };
+    let rest = &plist[start..];
+    let array = match (rest.find("<array>"), rest.find("</array>")) {
+        (Some(open), Some(close)) if open < close => &rest[open..close],
+        _ => return Vec::new(),
+    };
+    array
+        .split("<string>")
+        .skip(1)
+        .filter_map(|part| part.split("</string>").next())
+        .map(|value| value.replace("&amp;", "&").replace("&lt;", "<").replace("&gt;", ">"))
+        .collect()
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn registration(app: &tauri::AppHandle) -> Option<Registration> {
+    let entry: PathBuf = app
+        .path()
+        .home_dir()
+        .ok()?
+        .join(".config/autostart")
+        .join(format!("{}.desktop", app.package_info().name));
+    let desktop = std::fs::read_to_string(entry).ok()?;
+    let command = desktop.lines().find_map(|line| line.strip_prefix("Exec="))?;
+    Some(parse_command_line(AutostartScope::User, command))
+}
+
+pub fn details(app: &tauri::AppHandle) -> Result<AutostartDetails, String> {
+    let enabled = app.autolaunch().is_enabled().map_err(|e| e.to_string())?;
+    let registration = registration(app);
+    let current_path = current_path(app);
+    let stale = enabled
+        && match (&registration, &current_path) {
+            (Some(registration), Some(current)) => registration.path != *current,
+            _ => false,
+        };
+
+    Ok(AutostartDetails {
+        enabled,
+        minimized: registration.as_ref().is_some_and(|r| r.minimized),
+        args: saved_args(app),
+        scope: registration.as_ref().map(|r| r.scope),
+        registered_path: registration.map(|r| r.path),
+        current_path,
+        stale,
+    })
+}
+
+/// Re-registers the login item for the current executable.
+pub fn repair(app: &tauri::AppHandle) -> Result<(), String> {
+    app.autolaunch().disable().map_err(|e| e.to_string())?;
+    enable(app)
+}
+
+/// Re-registers the login item if it is enabled but points at an old executable, e.g. after
+/// an update moved the binary. Returns whether anything was repaired.
+pub fn verify(app: &tauri::AppHandle) -> Result<bool, String> {
+    let details = details(app)?;
+    if !details.stale {
+        return Ok(false);
+    }
+
+    let old_path = details.registered_path.unwrap_or_default();
+    let new_path = details.current_path.unwrap_or_default();
+    log::warn!("Stale autostart registration: {} -> {}", old_path, new_path);
+    repair(app)?;
+    startup::notify(
+        app,
+        "autostart-repaired",
+        json!({ "old_path": old_path, "new_path": new_path }),
+    );
+    Ok(true)
+}
+
+/// Whether the process arguments contain `flag`. The OS may add arguments of its own (e.g. a
+/// `-psn_…` process serial number on older macOS), so the position doesn't matter.
+fn has_arg(flag: &str) -> bool {
+    std::env::args()
+        .skip(1)
+        .any(|arg| arg.trim().trim_matches('"') == flag)
+}
+
+/// Reads the launch flags. The app starts in the tray when autostarted with `MINIMIZED_ARG`
+/// or when the setting asks for it, but only with a tray (not in safe mode, not turned off):
+/// otherwise there would be no way to bring the window up.
+pub fn init_launch(app: &tauri::AppHandle, tray_available: bool) {
+    let minimized_arg = has_arg(MINIMIZED_ARG);
+    let autostarted = minimized_arg || has_arg(AUTOSTART_ARG);
+    let requested = minimized_arg || settings::get(app, START_MINIMIZED_KEY).unwrap_or(false);
+    let minimized = requested && tray_available;
+    if autostarted {
+        log::info!("Launched by autostart");
+    }
+    if minimized {
+        log::info!("Starting minimized to the tray");
+    }
+    app.manage(LaunchFlags { autostarted, minimized });
+}
+
+pub fn started_minimized(app: &tauri::AppHandle) -> bool {
+    app.try_state::<LaunchFlags>()
+        .map(|flags| flags.minimized)
+        .unwrap_or(false)
+}
+
+#[tauri::command]
+pub async fn get_started_minimized(app: tauri::AppHandle) -> Result<bool, String> {
+    Ok(started_minimized(&app))
+}
+
+/// Whether this launch came from the login item, e.g. to skip the welcome screen.
+#[tauri::command]
+pub async fn was_autostarted(app: tauri::AppHandle) -> Result<bool, String> {
+    Ok(app.try_state::<LaunchFlags>().is_some_and(|flags| flags.autostarted))
+}
+
+/// Takes effect on the next launch.
+#[tauri::command]
+pub async fn set_start_minimized(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    settings::set(&app, START_MINIMIZED_KEY, enabled)
+}
+
+#[tauri::command]
+pub async fn get_start_minimized(app: tauri::AppHandle) -> Result<bool, String> {
+    Ok(settings::get(&app, START_MINIMIZED_KEY).unwrap_or(false))
+}
+
+/// Sets the arguments passed on login start, e.g. none to show the window. Only `--minimized`
+/// and `--safe-mode` are accepted. An enabled login item is re-registered right away.
+#[tauri::command]
+pub async fn set_autostart_args(app: tauri::AppHandle, args: Vec<String>) -> Result<(), String> {
+    let mut unique: Vec<String> = Vec::new();
+    for arg in args {
+        if !ALLOWED_ARGS.contains(&arg.as_str()) {
+            return Err(format!("unsupported autostart arg: {}", arg));
+        }
+        if !unique.contains(&arg) {
+            unique.push(arg);
+        }
+    }
+    settings::set(&app, ARGS_KEY, &unique)?;
+    if app.autolaunch().is_enabled().map_err(|e| e.to_string())? {
+        enable(&app)?;
+        log::info!("Autostart re-registered with args {:?}", unique);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_autostart_args(app: tauri::AppHandle) -> Result<Vec<String>, String> {
+    Ok(saved_args(&app))
+}
+
+#[tauri::command]
+pub async fn get_autostart_details(app: tauri::AppHandle) -> Result<AutostartDetails, String> {
+    details(&app)
+}
+
+#[tauri::command]
+pub async fn repair_autostart(app: tauri::AppHandle) -> Result<AutostartDetails, String> {
+    let before = details(&app)?;
+    if !before.enabled {
+        return Err("autostart is not enabled".to_string());
+    }
+    repair(&app)?;
+    log::info!(
+        "Autostart re-registered: {} -> {}",
+        before.registered_path.as_deref().unwrap_or("-"),
+        before.current_path.as_deref().unwrap_or("-")
+    );
+    details(&app)
+}
+
+#[tauri::command]
+pub async fn verify_autostart(app: tauri::AppHandle) -> Result<bool, String> {
+    verify(&app)
+}
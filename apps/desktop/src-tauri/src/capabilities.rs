@@ -0,0 +1,105 @@
+//! What the frontend is actually allowed to do on this build/platform, so features can be
+//! disabled up front instead of failing on first use.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use tauri_plugin_fs::FsExt;
+use tauri_plugin_notification::NotificationExt;
+
+use crate::plugins;
+
+const DEFAULT_CAPABILITY: &str = include_str!("../capabilities/default.json");
+const KEYCHAIN_SERVICE: &str = "de.gruenerator.desktop";
+/// Never written; looking it up only tells whether the credential store answers.
+const KEYCHAIN_PROBE_USER: &str = "capability-probe";
+
+#[derive(Deserialize)]
+struct CapabilityFile {
+    permissions: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct FsCapabilities {
+    read: bool,
+    write: bool,
+    scopes: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct Capabilities {
+    fs: FsCapabilities,
+    dialog: BTreeMap<String, bool>,
+    notification: String,
+    keychain: bool,
+    /// Optional plugins and whether they initialized; see `plugins`.
+    plugins: BTreeMap<&'static str, bool>,
+    platform: &'static str,
+}
+
+fn granted_permissions() -> Vec<String> {
+    serde_json::from_str::<CapabilityFile>(DEFAULT_CAPABILITY)
+        .map(|file| file.permissions)
+        .unwrap_or_default()
+}
+
+/// Whether the OS credential store can be reached. A missing entry still means it works; on
+/// Linux, a missing Secret Service provider shows up as a platform failure.
+fn keychain_available() -> bool {
+    let Ok(entry) = keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_PROBE_USER) else {
+        return false;
+    };
+    match entry.get_password() {
+        Ok(_) | Err(keyring::Error::NoEntry) => true,
+        Err(keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_)) => false,
+        Err(e) => {
+            log::warn!("Unexpected keychain probe result: {}", e);
+            true
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_capabilities(app: tauri::AppHandle) -> Result<Capabilities, String> {
+    let permissions = granted_permissions();
+    let granted = |permission: &str| permissions.iter().any(|p| p == permission);
+
+    let mut scopes: Vec<String> = app
+        .fs_scope()
+        .allowed_patterns()
+        .iter()
+        .map(|pattern| pattern.as_str().to_string())
+        .collect();
+    scopes.sort();
+
+    let dialog = ["open", "save", "message", "ask", "confirm"]
+        .iter()
+        .map(|kind| (kind.to_string(), granted(&format!("dialog:allow-{}", kind))))
+        .collect();
+
+    let notification = if !plugins::is_available(&app, plugins::NOTIFICATION) {
+        "unavailable".to_string()
+    } else if granted("notification:allow-notify") {
+        app.notification()
+            .permission_state()
+            .map(|state| state.to_string())
+            .unwrap_or_else(|_| "unknown".to_string())
+    } else {
+        "unavailable".to_string()
+    };
+
+    Ok(Capabilities {
+        fs: FsCapabilities {
+            read: granted("fs:allow-read-file") || granted("fs:allow-read-text-file"),
+            write: granted("fs:allow-write-file") || granted("fs:allow-write-text-file"),
+            scopes,
+        },
+        dialog,
+        notification,
+        // The OS credential store holds the export link signing key (see `share`) and WebDAV
+        // passwords.
+        keychain: keychain_available(),
+        plugins: plugins::status(&app),
+        platform: std::env::consts::OS,
+    })
+}
@@ -0,0 +1,75 @@
+//! Release notes for the "What's New" screen. The updater manifest only carries the latest
+//! version's notes, so the full history comes from a separate changelog manifest next to it:
+//! a JSON array of `{ "version", "date", "notes" }`, notes as raw markdown. It is fetched once
+//! per session.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+const CHANGELOG_ENDPOINT: &str = "https://gruenerator.eu/api/releases/changelog.json";
+const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    version: String,
+    /// Release date as given by the manifest, typically RFC 3339.
+    #[serde(default, alias = "pub_date")]
+    date: Option<String>,
+    #[serde(default)]
+    notes: String,
+}
+
+/// The manifest as fetched this session.
+#[derive(Default)]
+pub struct Changelog(Mutex<Option<Vec<ChangelogEntry>>>);
+
+async fn fetch() -> Result<Vec<ChangelogEntry>, String> {
+    let client = reqwest::Client::builder()
+        .https_only(true)
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .get(CHANGELOG_ENDPOINT)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("network_error: {}", e))?;
+    let body = response.text().await.map_err(|e| format!("network_error: {}", e))?;
+    serde_json::from_str(&body).map_err(|e| format!("invalid_changelog: {}", e))
+}
+
+/// Notes of every release newer than `since` (default: the running version), newest first.
+/// Entries whose version doesn't parse are left out.
+#[tauri::command]
+pub async fn get_changelog(app: tauri::AppHandle, since: Option<String>) -> Result<Vec<ChangelogEntry>, String> {
+    let since = match since {
+        Some(since) => Version::parse(since.trim().trim_start_matches('v'))
+            .map_err(|_| format!("invalid version: {}", since))?,
+        None => app.package_info().version.clone(),
+    };
+
+    let cached = app.state::<Changelog>().0.lock().unwrap().clone();
+    let entries = match cached {
+        Some(entries) => entries,
+        None => {
+            let entries = fetch().await?;
+            *app.state::<Changelog>().0.lock().unwrap() = Some(entries.clone());
+            entries
+        }
+    };
+
+    let mut newer: Vec<(Version, ChangelogEntry)> = entries
+        .into_iter()
+        .filter_map(|entry| {
+            let version = Version::parse(entry.version.trim_start_matches('v')).ok()?;
+            (version > since).then_some((version, entry))
+        })
+        .collect();
+    newer.sort_by(|(a, _), (b, _)| b.cmp(a));
+    Ok(newer.into_iter().map(|(_, entry)| entry).collect())
+}
@@ -0,0 +1,137 @@
+//! Opt-in history of the last few texts copied inside the app, so an earlier generation can
+//! be pasted again. Kept in memory only: nothing is written to disk and quitting clears it.
+//!
+//! Also the "generate from clipboard" entry point, which feeds whatever text was copied
+//! elsewhere into the generator. Its global shortcut is unbound until the user assigns one
+//! with `set_shortcut_override`.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use serde_json::json;
+use tauri::{Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::settings;
+
+const ENABLED_KEY: &str = "clipboard_history_enabled";
+const MAX_ENTRIES: usize = 10;
+const MAX_ENTRY_BYTES: usize = 256 * 1024;
+const MAX_GENERATE_CHARS: usize = 20_000;
+
+pub const GENERATE_SHORTCUT_ID: &str = "generate_from_clipboard";
+
+#[derive(Default)]
+pub struct ClipboardHistory(Mutex<VecDeque<String>>);
+
+impl ClipboardHistory {
+    fn entries(&self) -> Vec<String> {
+        self.0.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Adds `text` as the newest entry, unless it already is.
+    fn push(&self, text: String) -> bool {
+        let mut entries = self.0.lock().unwrap();
+        if entries.front() == Some(&text) {
+            return false;
+        }
+        entries.push_front(text);
+        entries.truncate(MAX_ENTRIES);
+        true
+    }
+}
+
+fn is_enabled(app: &tauri::AppHandle) -> bool {
+    settings::get(app, ENABLED_KEY).unwrap_or(false)
+}
+
+fn notify(app: &tauri::AppHandle) {
+    let _ = app.emit("clipboard-history-changed", app.state::<ClipboardHistory>().entries());
+}
+
+#[tauri::command]
+pub async fn set_clipboard_history_enabled(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    settings::set(&app, ENABLED_KEY, enabled)?;
+    if !enabled {
+        app.state::<ClipboardHistory>().0.lock().unwrap().clear();
+        notify(&app);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_clipboard_history_enabled(app: tauri::AppHandle) -> Result<bool, String> {
+    Ok(is_enabled(&app))
+}
+
+/// Called by the frontend after an in-app copy. Ignored while the history is disabled.
+#[tauri::command]
+pub async fn record_clipboard_copy(app: tauri::AppHandle, text: String) -> Result<(), String> {
+    if !is_enabled(&app) || text.trim().is_empty() || text.len() > MAX_ENTRY_BYTES {
+        return Ok(());
+    }
+    if app.state::<ClipboardHistory>().push(text) {
+        notify(&app);
+    }
+    Ok(())
+}
+
+/// Newest first.
+#[tauri::command]
+pub async fn get_clipboard_history(history: tauri::State<'_, ClipboardHistory>) -> Result<Vec<String>, String> {
+    Ok(history.entries())
+}
+
+/// Puts entry `index` back on the system clipboard and makes it the newest entry.
+#[tauri::command]
+pub async fn clipboard_history_restore(app: tauri::AppHandle, index: usize) -> Result<(), String> {
+    let history = app.state::<ClipboardHistory>();
+    let text = {
+        let mut entries = history.0.lock().unwrap();
+        let text = entries
+            .remove(index)
+            .ok_or_else(|| format!("no clipboard entry at index {}", index))?;
+        entries.push_front(text.clone());
+        text
+    };
+    app.clipboard().write_text(text).map_err(|e| e.to_string())?;
+    notify(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn clear_clipboard_history(app: tauri::AppHandle) -> Result<(), String> {
+    app.state::<ClipboardHistory>().0.lock().unwrap().clear();
+    notify(&app);
+    Ok(())
+}
+
+/// Brings the main window forward and emits `quick-generate` with the trimmed clipboard text,
+/// cut to [`MAX_GENERATE_CHARS`]. Without usable text, `quick-generate-rejected` tells the UI
+/// why (`not_text` or `empty`).
+pub fn quick_generate(app: &tauri::AppHandle) -> Result<(), String> {
+    if let Some(main_window) = app.get_webview_window("main") {
+        let _ = main_window.unminimize();
+        let _ = main_window.show();
+        let _ = main_window.set_focus();
+    }
+
+    let text = match app.clipboard().read_text() {
+        Ok(text) if !text.trim().is_empty() => text,
+        result => {
+            let reason = if result.is_ok() { "empty" } else { "not_text" };
+            let _ = app.emit("quick-generate-rejected", json!({ "reason": reason }));
+            return Err(format!("clipboard_{}", reason));
+        }
+    };
+    let text = text.trim();
+    let truncated = text.chars().count() > MAX_GENERATE_CHARS;
+    let text: String = text.chars().take(MAX_GENERATE_CHARS).collect();
+    app.emit("quick-generate", json!({ "text": text, "truncated": truncated }))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn generate_from_clipboard(app: tauri::AppHandle) -> Result<(), String> {
+    quick_generate(&app)
+}
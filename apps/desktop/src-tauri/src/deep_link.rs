@@ -0,0 +1,273 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{Emitter, Manager};
+
+use crate::{settings, share, startup};
+
+const DEFAULT_SCHEME: &str = "gruenerator";
+/// Auth callbacks older than this are dropped instead of delivered; the login attempt they
+/// belong to has long timed out.
+const MAX_PENDING_AGE: Duration = Duration::from_secs(60);
+const MAX_PARAM_LEN: usize = 256;
+const OAUTH_STATE_KEY: &str = "oauth_state";
+/// How long a started login waits for its callback before it is rejected as `state_expired`.
+const OAUTH_STATE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Auth callbacks that arrived before the frontend listened for them, with their arrival time.
+/// `None` once `frontend_ready` has been called.
+pub struct PendingAuthLinks(Mutex<Option<Vec<(String, Instant)>>>);
+
+impl Default for PendingAuthLinks {
+    fn default() -> Self {
+        Self(Mutex::new(Some(Vec::new())))
+    }
+}
+
+/// The `state` the frontend sent along with the login it started; a callback is only passed on
+/// if it carries the same value. Persisted so it survives a login link that cold-starts the
+/// app, and removed once matched, so a callback can't be replayed.
+#[derive(Serialize, Deserialize)]
+struct ExpectedState {
+    state: String,
+    expires_at: u64,
+}
+
+#[derive(Clone, Serialize)]
+pub struct AuthCallback {
+    code: Option<String>,
+    state: String,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+/// Checks an auth callback against the expected `state` and emits `deep-link-auth` with its
+/// decoded parameters, or `deep-link-auth-error` with the reason it was rejected. Errors the
+/// identity provider reports (`error`, `error_description`) are passed on in `deep-link-auth`.
+fn deliver_auth(app: &tauri::AppHandle, url: &str) {
+    let param = |url: &url::Url, name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+            .filter(|value| !value.is_empty())
+    };
+    let result = url::Url::parse(url)
+        .map_err(|_| "invalid_callback")
+        .and_then(|url| {
+            let state = param(&url, "state").ok_or("missing_state")?;
+            let expected = settings::get::<ExpectedState>(app, OAUTH_STATE_KEY)
+                .filter(|expected| expected.state == state)
+                .ok_or("state_mismatch")?;
+            let _ = settings::remove(app, OAUTH_STATE_KEY);
+            if expected.expires_at < crate::now_millis() {
+                return Err("state_expired");
+            }
+            Ok(AuthCallback {
+                code: param(&url, "code"),
+                state,
+                error: param(&url, "error"),
+                error_description: param(&url, "error_description"),
+            })
+        });
+    match result {
+        Ok(callback) => {
+            let _ = app.emit("deep-link-auth", callback);
+        }
+        Err(reason) => {
+            log::warn!("Rejected auth callback: {}", reason);
+            let _ = app.emit("deep-link-auth-error", json!({ "reason": reason }));
+        }
+    }
+}
+
+/// The scheme registered for this build, read from the deep-link plugin config so staging
+/// builds (`tauri.staging.conf.json`) never answer production links and vice versa.
+pub fn active_scheme<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> String {
+    let desktop = app
+        .config()
+        .plugins
+        .0
+        .get("deep-link")
+        .and_then(|config| config.get("desktop"));
+
+    let schemes = match desktop {
+        Some(Value::Array(protocols)) => protocols.first().and_then(|p| p.get("schemes")),
+        Some(protocol) => protocol.get("schemes"),
+        None => None,
+    };
+
+    schemes
+        .and_then(|schemes| schemes.get(0))
+        .and_then(Value::as_str)
+        .unwrap_or(DEFAULT_SCHEME)
+        .to_string()
+}
+
+pub fn handle_url(app: &tauri::AppHandle, scheme: &str, url: &url::Url) {
+    if url.scheme() != scheme {
+        log::warn!("Ignoring deep link for foreign scheme: {}", url.scheme());
+        return;
+    }
+
+    let url_str = url.to_string();
+    if url.host_str() == Some("auth") && url.path() == "/callback" {
+        let pending = app.state::<PendingAuthLinks>();
+        let mut queue = pending.0.lock().unwrap();
+        match queue.as_mut() {
+            Some(queue) => queue.push((url_str, Instant::now())),
+            None => deliver_auth(app, &url_str),
+        }
+    } else if url.host_str() == Some("open") && url.path().trim_matches('/').is_empty() {
+        open_document(app, url);
+    } else {
+        navigate(app, url);
+    }
+}
+
+/// Deep links also arrive as command-line arguments of a second instance, which the
+/// single-instance plugin hands to the running one.
+pub fn handle_args(app: &tauri::AppHandle, args: &[String]) {
+    let scheme = active_scheme(app);
+    for arg in args {
+        if let Ok(url) = url::Url::parse(arg) {
+            if url.scheme() == scheme {
+                handle_url(app, &scheme, &url);
+            }
+        }
+    }
+}
+
+/// Brings the main window forward if the app was already running; on a cold start the splash
+/// reveals it once it has loaded.
+fn bring_forward(app: &tauri::AppHandle) {
+    if startup::is_marked(app, startup::MAIN_SHOWN) {
+        if let Some(main_window) = app.get_webview_window("main") {
+            let _ = main_window.show();
+            let _ = main_window.unminimize();
+            let _ = main_window.set_focus();
+        }
+    }
+}
+
+fn is_valid_id(value: &str, max_len: usize) -> bool {
+    !value.is_empty()
+        && value.len() <= max_len
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// `<scheme>://open?doc=<id>&section=<anchor>` opens a document, optionally scrolled to a
+/// section. A malformed section still opens the document, just at the top. Links carrying a
+/// `token` (see [`share`]) only open while the token is valid; otherwise
+/// `open-link-rejected` reports why. Like all navigation events, these are buffered until the
+/// frontend is ready, so links that launched the app aren't lost.
+fn open_document(app: &tauri::AppHandle, url: &url::Url) {
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+
+    let Some(doc) = param("doc").filter(|doc| is_valid_id(doc, 64)) else {
+        log::warn!("Ignoring open link without a valid document id");
+        return;
+    };
+    if let Some(token) = param("token") {
+        if let Err(reason) = share::validate(app, &doc, &token) {
+            log::warn!("Rejected link to {}: {}", doc, reason);
+            startup::notify(app, "open-link-rejected", json!({ "doc": doc, "reason": reason }));
+            return;
+        }
+    }
+    let requested_section = param("section");
+    let section = requested_section
+        .clone()
+        .filter(|section| is_valid_id(section, 128));
+
+    bring_forward(app);
+    startup::notify(app, "open-document", json!({ "doc": doc, "section": section }));
+
+    if let (Some(requested), None) = (requested_section, &section) {
+        startup::notify(app, "section-not-found", json!({ "doc": doc, "section": requested }));
+    }
+}
+
+/// The route and parameters of a navigation link:
+/// - `<scheme>://open/<type>/<id>`, e.g. `open/antrag/123`: `open` with `type` and `id`
+/// - `<scheme>://new?type=<type>`, e.g. `new?type=pressemitteilung`: `new` with `type`
+///
+/// Further query parameters are passed along. `None` for anything else.
+fn route(url: &url::Url) -> Option<(&'static str, serde_json::Map<String, Value>)> {
+    let mut params = serde_json::Map::new();
+    for (key, value) in url.query_pairs() {
+        if is_valid_id(&key, 64) && value.len() <= MAX_PARAM_LEN {
+            params.insert(key.into_owned(), Value::String(value.into_owned()));
+        }
+    }
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.filter(|segment| !segment.is_empty()).collect())
+        .unwrap_or_default();
+
+    let route = match (url.host_str()?, segments.as_slice()) {
+        ("open", [kind, id]) if is_valid_id(kind, 64) && is_valid_id(id, 64) => {
+            params.insert("type".to_string(), json!(kind));
+            params.insert("id".to_string(), json!(id));
+            "open"
+        }
+        ("new", []) => "new",
+        _ => return None,
+    };
+    let kind = params.get("type").and_then(Value::as_str)?;
+    is_valid_id(kind, 64).then_some((route, params))
+}
+
+/// Emits `deep-link-navigate` with `{ route, params }` for known routes and brings the window
+/// forward; anything else is reported as `deep-link-unknown` with the raw URL.
+fn navigate(app: &tauri::AppHandle, url: &url::Url) {
+    let Some((route, params)) = route(url) else {
+        log::warn!("Unknown deep link: {}", url);
+        startup::notify(app, "deep-link-unknown", url.to_string());
+        return;
+    };
+    bring_forward(app);
+    startup::notify(app, "deep-link-navigate", json!({ "route": route, "params": params }));
+}
+
+/// Called by the frontend once its listeners are registered: delivers the auth callbacks
+/// received so far as `deep-link-auth` (stale ones are dropped) and the buffered startup
+/// events. Later calls, e.g. after a reload, have nothing left to deliver.
+#[tauri::command]
+pub async fn frontend_ready(app: tauri::AppHandle) -> Result<(), String> {
+    let pending = app.state::<PendingAuthLinks>().0.lock().unwrap().take();
+    for (url, received) in pending.unwrap_or_default() {
+        if received.elapsed() > MAX_PENDING_AGE {
+            log::warn!("Dropping auth callback received {} s ago", received.elapsed().as_secs());
+            continue;
+        }
+        deliver_auth(&app, &url);
+    }
+    startup::flush(&app);
+    Ok(())
+}
+
+/// The deep-link scheme of this build (`gruenerator`, or `gruenerator-staging` for staging), so
+/// the frontend can build redirect URLs this build actually receives.
+#[tauri::command]
+pub async fn get_deep_link_scheme(app: tauri::AppHandle) -> Result<String, String> {
+    Ok(active_scheme(&app))
+}
+
+/// Remembers the `state` of a login the frontend is about to start, replacing any earlier
+/// one; see [`ExpectedState`].
+#[tauri::command]
+pub async fn set_oauth_state(app: tauri::AppHandle, state: String) -> Result<(), String> {
+    if state.trim().is_empty() {
+        return Err("state is required".to_string());
+    }
+    let expires_at = crate::now_millis() + OAUTH_STATE_TTL.as_millis() as u64;
+    settings::set(&app, OAUTH_STATE_KEY, ExpectedState { state, expires_at })
+}
@@ -0,0 +1,45 @@
+//! "Nicht stören" master switch. What interrupts the user on the app's own initiative checks
+//! [`is_enabled`] first: native notifications and reminders (`notification`), taskbar
+//! flashing (`window::flash_window`), the background `update-available` announcement
+//! (`updater`) and the retry queue. Bringing the window forward in response to the user
+//! (tray, global shortcuts, opening a file or link, clicking a notification) is not affected.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tauri::{Emitter, Manager};
+
+use crate::{app_state, settings};
+
+const DND_KEY: &str = "do_not_disturb";
+
+pub struct DoNotDisturb(AtomicBool);
+
+pub fn init(app: &tauri::AppHandle) {
+    let enabled = settings::get(app, DND_KEY).unwrap_or(false);
+    app.manage(DoNotDisturb(AtomicBool::new(enabled)));
+}
+
+pub fn is_enabled(app: &tauri::AppHandle) -> bool {
+    app.try_state::<DoNotDisturb>()
+        .map(|dnd| dnd.0.load(Ordering::SeqCst))
+        .unwrap_or(false)
+}
+
+pub fn set_enabled(app: &tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let dnd = app.state::<DoNotDisturb>();
+    if dnd.0.swap(enabled, Ordering::SeqCst) != enabled {
+        let _ = app.emit("dnd-changed", enabled);
+        app_state::refresh(app);
+    }
+    settings::set(app, DND_KEY, enabled)
+}
+
+#[tauri::command]
+pub async fn set_do_not_disturb(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    set_enabled(&app, enabled)
+}
+
+#[tauri::command]
+pub async fn get_do_not_disturb(app: tauri::AppHandle) -> Result<bool, String> {
+    Ok(is_enabled(&app))
+}
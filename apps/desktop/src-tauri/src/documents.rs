@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{Emitter, Manager};
+
+use crate::{menu, settings, tray};
+
+const PINNED_KEY: &str = "pinned_documents";
+const LOCKED_KEY: &str = "locked_documents";
+const UNLOCK_TOKEN_TTL_MS: u64 = 60_000;
+const MAX_PINNED: usize = 10;
+const MAX_LABEL_CHARS: usize = 40;
+
+pub const PINNED_ID_PREFIX: &str = "pinned:";
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PinnedDocument {
+    pub id: String,
+    pub title: String,
+}
+
+pub fn pinned(app: &tauri::AppHandle) -> Vec<PinnedDocument> {
+    settings::get(app, PINNED_KEY).unwrap_or_default()
+}
+
+/// Menu labels are shared by the tray and the Datei menu, so both truncate the same way.
+pub fn menu_label(title: &str) -> String {
+    let title = title.trim();
+    if title.chars().count() <= MAX_LABEL_CHARS {
+        return title.to_string();
+    }
+    let truncated: String = title.chars().take(MAX_LABEL_CHARS - 1).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+pub fn refresh_menus(app: &tauri::AppHandle) -> Result<(), String> {
+    menu::rebuild(app).map_err(|e| e.to_string())?;
+    tray::rebuild_menu(app).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn pin_document(app: tauri::AppHandle, id: String, title: String) -> Result<(), String> {
+    if id.trim().is_empty() || title.trim().is_empty() {
+        return Err("id and title are required".to_string());
+    }
+
+    let mut documents = pinned(&app);
+    if let Some(existing) = documents.iter_mut().find(|doc| doc.id == id) {
+        existing.title = title;
+    } else {
+        if documents.len() >= MAX_PINNED {
+            return Err(format!("at most {} documents can be pinned", MAX_PINNED));
+        }
+        documents.push(PinnedDocument { id, title });
+    }
+
+    settings::set(&app, PINNED_KEY, &documents)?;
+    refresh_menus(&app)
+}
+
+#[tauri::command]
+pub async fn unpin_document(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    let mut documents = pinned(&app);
+    documents.retain(|doc| doc.id != id);
+    settings::set(&app, PINNED_KEY, &documents)?;
+    refresh_menus(&app)
+}
+
+#[tauri::command]
+pub async fn list_pinned_documents(app: tauri::AppHandle) -> Result<Vec<PinnedDocument>, String> {
+    Ok(pinned(&app))
+}
+
+/// Stable hex digest of a document's content, used for dirty tracking, the thumbnail cache
+/// and skipping unchanged autosaves.
+pub fn hash_content(text: &str) -> String {
+    blake3::hash(text.as_bytes()).to_hex().to_string()
+}
+
+#[tauri::command]
+pub async fn content_hash(text: String) -> Result<String, String> {
+    Ok(hash_content(&text))
+}
+
+/// Document ids end up in file names, so only a conservative character set is accepted.
+pub fn validate_id(id: &str) -> Result<(), String> {
+    let valid = !id.is_empty()
+        && id.len() <= 64
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("invalid document id: {}", id))
+    }
+}
+
+fn locked_ids(app: &tauri::AppHandle) -> Vec<String> {
+    settings::get(app, LOCKED_KEY).unwrap_or_default()
+}
+
+/// Locked documents are read-only: anything that writes a document checks this first and
+/// refuses with `locked`.
+pub fn is_locked(app: &tauri::AppHandle, id: &str) -> bool {
+    locked_ids(app).iter().any(|locked_id| locked_id == id)
+}
+
+/// Pending unlock confirmations: document id → (token, expiry).
+#[derive(Default)]
+pub struct UnlockTokens(Mutex<HashMap<String, (String, u64)>>);
+
+#[tauri::command]
+pub async fn request_unlock_token(app: tauri::AppHandle, id: String) -> Result<String, String> {
+    validate_id(&id)?;
+    if !is_locked(&app, &id) {
+        return Err("not_locked".to_string());
+    }
+    let now = crate::now_millis();
+    let token = blake3::hash(format!("{}:{}:{:?}", id, now, std::time::Instant::now()).as_bytes())
+        .to_hex()[..16]
+        .to_string();
+    app.state::<UnlockTokens>()
+        .0
+        .lock()
+        .unwrap()
+        .insert(id, (token.clone(), now + UNLOCK_TOKEN_TTL_MS));
+    Ok(token)
+}
+
+/// Locking takes effect immediately; unlocking needs a token from
+/// [`request_unlock_token`] so it can't happen by accident.
+#[tauri::command]
+pub async fn set_document_locked(
+    app: tauri::AppHandle,
+    id: String,
+    locked: bool,
+    confirmation_token: Option<String>,
+) -> Result<(), String> {
+    validate_id(&id)?;
+    let mut documents = locked_ids(&app);
+
+    if locked {
+        if !documents.contains(&id) {
+            documents.push(id.clone());
+        }
+    } else {
+        let pending = app.state::<UnlockTokens>().0.lock().unwrap().remove(&id);
+        let confirmed = match (pending, confirmation_token) {
+            (Some((expected, expires_at)), Some(token)) => {
+                expected == token && crate::now_millis() <= expires_at
+            }
+            _ => false,
+        };
+        if !confirmed {
+            return Err("confirmation_required".to_string());
+        }
+        documents.retain(|locked_id| *locked_id != id);
+    }
+
+    settings::set(&app, LOCKED_KEY, &documents)?;
+    let _ = app.emit("document-locked", json!({ "id": id, "locked": locked }));
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn is_document_locked(app: tauri::AppHandle, id: String) -> Result<bool, String> {
+    Ok(is_locked(&app, &id))
+}
@@ -0,0 +1,85 @@
+//! Autosaved drafts, one file per document under `<data dir>/drafts`. Writes go through a
+//! temporary file and a rename so a crash mid-save never leaves a half-written draft, and
+//! are skipped entirely when the content hash matches what is already on disk.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use tauri::Manager;
+
+use crate::{documents, paths};
+
+const DRAFTS_DIR: &str = "drafts";
+
+fn draft_path(app: &tauri::AppHandle, id: &str) -> Result<PathBuf, String> {
+    documents::validate_id(id)?;
+    Ok(paths::data_dir(app)?.join(DRAFTS_DIR).join(format!("{}.html", id)))
+}
+
+/// Content hash of the last draft written per document.
+#[derive(Default)]
+pub struct SavedDraftHashes(Mutex<HashMap<String, String>>);
+
+fn ensure_writable(app: &tauri::AppHandle, id: &str) -> Result<(), String> {
+    if documents::is_locked(app, id) {
+        return Err("locked".to_string());
+    }
+    Ok(())
+}
+
+/// Returns whether anything was written; unchanged content is skipped.
+#[tauri::command]
+pub async fn save_draft(app: tauri::AppHandle, id: String, content: String) -> Result<bool, String> {
+    let path = draft_path(&app, &id)?;
+    ensure_writable(&app, &id)?;
+
+    let hash = documents::hash_content(&content);
+    let saved_hash = app.state::<SavedDraftHashes>().0.lock().unwrap().get(&id).cloned();
+    let on_disk = match saved_hash {
+        Some(saved_hash) => Some(saved_hash),
+        // First save since launch: compare against the existing file instead.
+        None => fs::read_to_string(&path)
+            .ok()
+            .map(|existing| documents::hash_content(&existing)),
+    };
+    if on_disk.as_deref() == Some(hash.as_str()) {
+        return Ok(false);
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let tmp = path.with_extension("html.tmp");
+        fs::write(&tmp, content).map_err(|e| e.to_string())?;
+        fs::rename(&tmp, &path).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    app.state::<SavedDraftHashes>().0.lock().unwrap().insert(id, hash);
+    Ok(true)
+}
+
+#[tauri::command]
+pub async fn load_draft(app: tauri::AppHandle, id: String) -> Result<Option<String>, String> {
+    let path = draft_path(&app, &id)?;
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn delete_draft(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    let path = draft_path(&app, &id)?;
+    ensure_writable(&app, &id)?;
+    app.state::<SavedDraftHashes>().0.lock().unwrap().remove(&id);
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+        _ => Ok(()),
+    }
+}
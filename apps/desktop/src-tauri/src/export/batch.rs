@@ -0,0 +1,165 @@
+//! Exports a set of documents into one folder as a single cancellable task. A failing item is
+//! recorded and skipped; the rest of the batch still runs.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+
+use super::{remember_dir, write_as, DocumentMetadata, ExportMetadata, FORMATS};
+use crate::tasks::{TaskKind, TaskRegistry};
+
+const MAX_FILE_STEM_CHARS: usize = 100;
+
+#[derive(Deserialize)]
+pub struct ExportItem {
+    pub id: String,
+    /// File name without extension; falls back to the id.
+    pub name: Option<String>,
+    pub html: String,
+    pub metadata: Option<DocumentMetadata>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct ExportedItem {
+    id: String,
+    path: String,
+}
+
+#[derive(Clone, Serialize)]
+pub struct FailedItem {
+    id: String,
+    error: String,
+}
+
+#[derive(Clone, Serialize)]
+pub struct BatchSummary {
+    task_id: String,
+    exported: Vec<ExportedItem>,
+    failed: Vec<FailedItem>,
+    cancelled: bool,
+}
+
+#[derive(Clone, Serialize)]
+struct BatchProgress<'a> {
+    task_id: &'a str,
+    id: &'a str,
+    done: usize,
+    total: usize,
+    error: Option<&'a str>,
+}
+
+fn file_stem(item: &ExportItem) -> String {
+    let name = item.name.as_deref().unwrap_or(&item.id);
+    let stem: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .take(MAX_FILE_STEM_CHARS)
+        .collect();
+    let stem = stem.trim().trim_matches('.').to_string();
+    if stem.is_empty() {
+        "Dokument".to_string()
+    } else {
+        stem
+    }
+}
+
+/// A path in `dir` that neither exists nor was handed out earlier in this batch.
+fn unique_path(dir: &Path, stem: &str, extension: &str, taken: &mut HashSet<PathBuf>) -> PathBuf {
+    let mut candidate = dir.join(format!("{}.{}", stem, extension));
+    let mut counter = 2;
+    while candidate.exists() || taken.contains(&candidate) {
+        candidate = dir.join(format!("{} ({}).{}", stem, counter, extension));
+        counter += 1;
+    }
+    taken.insert(candidate.clone());
+    candidate
+}
+
+#[tauri::command]
+pub async fn export_batch(
+    app: tauri::AppHandle,
+    items: Vec<ExportItem>,
+    dir: String,
+    format: String,
+) -> Result<BatchSummary, String> {
+    if !FORMATS.contains(&format.as_str()) {
+        return Err(format!("unsupported export format: {}", format));
+    }
+    let dir = PathBuf::from(dir);
+    if !dir.is_dir() {
+        return Err(format!("not a directory: {}", dir.display()));
+    }
+
+    let task = app.state::<TaskRegistry>().start(
+        &app,
+        TaskKind::Export,
+        format!("Export von {} Dokumenten", items.len()),
+        true,
+    );
+    let items: Vec<(ExportItem, ExportMetadata)> = items
+        .into_iter()
+        .map(|mut item| {
+            let metadata = ExportMetadata::new(&app, item.metadata.take());
+            (item, metadata)
+        })
+        .collect();
+
+    let summary = tauri::async_runtime::spawn_blocking(move || {
+        let total = items.len();
+        let mut taken = HashSet::new();
+        let mut summary = BatchSummary {
+            task_id: task.id().to_string(),
+            exported: Vec::new(),
+            failed: Vec::new(),
+            cancelled: false,
+        };
+
+        for (done, (item, metadata)) in items.into_iter().enumerate() {
+            if task.is_cancelled() {
+                summary.cancelled = true;
+                break;
+            }
+
+            let path = unique_path(&dir, &file_stem(&item), &format, &mut taken);
+            let result = write_as(&format, &path, &item.html, &metadata);
+            let _ = app.emit(
+                "batch-export-progress",
+                BatchProgress {
+                    task_id: task.id(),
+                    id: &item.id,
+                    done: done + 1,
+                    total,
+                    error: result.as_ref().err().map(String::as_str),
+                },
+            );
+            match result {
+                Ok(()) => summary.exported.push(ExportedItem {
+                    id: item.id,
+                    path: path.to_string_lossy().to_string(),
+                }),
+                Err(error) => {
+                    log::warn!("Batch export of {} failed: {}", item.id, error);
+                    let _ = std::fs::remove_file(&path);
+                    summary.failed.push(FailedItem { id: item.id, error });
+                }
+            }
+            task.set_progress((done + 1) as f64 / total as f64);
+        }
+
+        if !summary.exported.is_empty() {
+            remember_dir(&app, &dir);
+        }
+        let _ = app.emit("batch-export-finished", summary.clone());
+        summary
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(summary)
+}
@@ -0,0 +1,163 @@
+//! Minimal WordprocessingML package: one document part plus core/app properties.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::{write_error, ExportMetadata};
+use crate::html::{self, BlockKind};
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+<Default Extension="xml" ContentType="application/xml"/>
+<Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
+<Override PartName="/docProps/core.xml" ContentType="application/vnd.openxmlformats-package.core-properties+xml"/>
+<Override PartName="/docProps/app.xml" ContentType="application/vnd.openxmlformats-officedocument.extended-properties+xml"/>
+</Types>"#;
+
+const RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
+<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" Target="docProps/core.xml"/>
+<Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/extended-properties" Target="docProps/app.xml"/>
+</Relationships>"#;
+
+pub fn write(path: &Path, html: &str, metadata: &ExportMetadata) -> Result<(), String> {
+    let file = File::create(path).map_err(|e| write_error(path, e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let parts = [
+        ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+        ("_rels/.rels", RELS.to_string()),
+        ("docProps/core.xml", core_properties(metadata)),
+        ("docProps/app.xml", app_properties(metadata)),
+        ("word/document.xml", document(html)),
+    ];
+    for (name, content) in parts {
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        zip.write_all(content.as_bytes()).map_err(|e| e.to_string())?;
+    }
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn document(html: &str) -> String {
+    let mut body = String::new();
+    for block in html::to_blocks(html) {
+        let (size, bold, text) = match block.kind {
+            BlockKind::Heading(1) => (36, true, block.text),
+            BlockKind::Heading(2) => (30, true, block.text),
+            BlockKind::Heading(_) => (26, true, block.text),
+            BlockKind::ListItem => (22, false, format!("• {}", block.text)),
+            BlockKind::Paragraph => (22, false, block.text),
+        };
+        body.push_str(&format!(
+            "<w:p><w:r><w:rPr>{}<w:sz w:val=\"{}\"/></w:rPr><w:t xml:space=\"preserve\">{}</w:t></w:r></w:p>",
+            if bold { "<w:b/>" } else { "" },
+            size,
+            escape(&text)
+        ));
+    }
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<w:document xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\"><w:body>{}</w:body></w:document>",
+        body
+    )
+}
+
+fn core_properties(metadata: &ExportMetadata) -> String {
+    let mut properties = String::new();
+    if let Some(title) = &metadata.title {
+        properties.push_str(&format!("<dc:title>{}</dc:title>", escape(title)));
+    }
+    if let Some(author) = &metadata.author {
+        properties.push_str(&format!("<dc:creator>{}</dc:creator>", escape(author)));
+    }
+    properties.push_str(&format!(
+        "<dcterms:created xsi:type=\"dcterms:W3CDTF\">{}</dcterms:created>",
+        metadata.created_at.format("%Y-%m-%dT%H:%M:%SZ")
+    ));
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<cp:coreProperties xmlns:cp=\"http://schemas.openxmlformats.org/package/2006/metadata/core-properties\" xmlns:dc=\"http://purl.org/dc/elements/1.1/\" xmlns:dcterms=\"http://purl.org/dc/terms/\" xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\">{}</cp:coreProperties>",
+        properties
+    )
+}
+
+fn app_properties(metadata: &ExportMetadata) -> String {
+    let version = app_version(&metadata.app_version)
+        .map(|version| format!("<AppVersion>{}</AppVersion>", version))
+        .unwrap_or_default();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<Properties xmlns=\"http://schemas.openxmlformats.org/officeDocument/2006/extended-properties\"><Application>{}</Application>{}</Properties>",
+        escape(&metadata.app_name),
+        version
+    )
+}
+
+/// `AppVersion` must have the form `XX.YYYY` (major.minor); versions that don't fit are left
+/// out rather than written in a shape Word rejects.
+fn app_version(version: &str) -> Option<String> {
+    let mut parts = version.split('.');
+    let major: u32 = parts.next()?.parse().ok()?;
+    let minor: u32 = parts.next()?.parse().ok()?;
+    (major < 100 && minor < 10_000).then(|| format!("{:02}.{:04}", major, minor))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn metadata() -> ExportMetadata {
+        ExportMetadata {
+            title: Some("Pressemitteilung: Grüne & Verkehr".to_string()),
+            author: Some("Kreisverband Köln".to_string()),
+            created_at: chrono::Utc.with_ymd_and_hms(2026, 3, 29, 8, 30, 0).unwrap(),
+            app_name: "Grünerator".to_string(),
+            app_version: "1.0.1".to_string(),
+        }
+    }
+
+    fn read_part(path: &Path, name: &str) -> String {
+        let mut archive = zip::ZipArchive::new(File::open(path).unwrap()).unwrap();
+        let mut content = String::new();
+        archive.by_name(name).unwrap().read_to_string(&mut content).unwrap();
+        content
+    }
+
+    #[test]
+    fn metadata_reads_back_from_core_properties() {
+        let path = std::env::temp_dir().join(format!("gruenerator-docx-{}.docx", std::process::id()));
+        write(&path, "<h1>Titel</h1><p>Text</p>", &metadata()).unwrap();
+        let core = read_part(&path, "docProps/core.xml");
+        let app = read_part(&path, "docProps/app.xml");
+        let _ = std::fs::remove_file(&path);
+
+        assert!(core.contains("<dc:title>Pressemitteilung: Grüne &amp; Verkehr</dc:title>"));
+        assert!(core.contains("<dc:creator>Kreisverband Köln</dc:creator>"));
+        assert!(core.contains(">2026-03-29T08:30:00Z</dcterms:created>"));
+        assert!(app.contains("<Application>Grünerator</Application>"));
+        assert!(app.contains("<AppVersion>01.0000</AppVersion>"));
+    }
+
+    #[test]
+    fn app_version_uses_the_extended_properties_format() {
+        assert_eq!(app_version("2.13.4").as_deref(), Some("02.0013"));
+        assert_eq!(app_version("100.0.0"), None);
+        assert_eq!(app_version("dev"), None);
+    }
+}
@@ -0,0 +1,175 @@
+//! Native document export. The frontend hands over the rendered HTML and a target path;
+//! layout is deliberately simple (headings, paragraphs, lists) so exports stay readable in
+//! any office suite without shipping a browser engine.
+
+pub mod batch;
+mod docx;
+mod pdf;
+pub mod stream;
+pub mod webdav;
+
+pub use stream::StreamingExports;
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use tauri::{Emitter, Manager};
+
+use crate::settings;
+use crate::tasks::{TaskKind, TaskRegistry};
+
+const MAX_METADATA_LEN: usize = 256;
+const LAST_DIR_KEY: &str = "last_export_dir";
+
+/// Formats accepted wherever the frontend picks one by name.
+pub(crate) const FORMATS: &[&str] = &["html", "pdf", "docx"];
+
+/// Optional document properties embedded into exported files.
+#[derive(Clone, Default, Deserialize)]
+pub struct DocumentMetadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    /// RFC 3339 timestamp; defaults to the export time.
+    pub created_at: Option<String>,
+}
+
+/// Metadata after sanitizing, with empty fields dropped and the producing app filled in.
+pub(crate) struct ExportMetadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub app_name: String,
+    pub app_version: String,
+}
+
+impl ExportMetadata {
+    fn new(app: &tauri::AppHandle, metadata: Option<DocumentMetadata>) -> Self {
+        let metadata = metadata.unwrap_or_default();
+        let created_at = metadata
+            .created_at
+            .as_deref()
+            .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())
+            .map(|value| value.with_timezone(&chrono::Utc))
+            .unwrap_or_else(chrono::Utc::now);
+
+        Self {
+            title: sanitize(metadata.title),
+            author: sanitize(metadata.author),
+            created_at,
+            app_name: app.package_info().name.clone(),
+            app_version: app.package_info().version.to_string(),
+        }
+    }
+
+    pub fn producer(&self) -> String {
+        format!("{} {}", self.app_name, self.app_version)
+    }
+}
+
+fn sanitize(value: Option<String>) -> Option<String> {
+    let value: String = value?
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_METADATA_LEN)
+        .collect();
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// Names a missing write permission explicitly; the OS message alone ("Access is denied")
+/// doesn't say which file.
+pub(crate) fn write_error(path: &Path, e: std::io::Error) -> String {
+    match e.kind() {
+        std::io::ErrorKind::PermissionDenied => format!("permission_denied: {}", path.display()),
+        _ => e.to_string(),
+    }
+}
+
+pub(crate) fn write_as(
+    format: &str,
+    path: &Path,
+    html: &str,
+    metadata: &ExportMetadata,
+) -> Result<(), String> {
+    match format {
+        "html" => std::fs::write(path, html).map_err(|e| write_error(path, e)),
+        "pdf" => pdf::write(path, html, metadata),
+        "docx" => docx::write(path, html, metadata),
+        _ => Err(format!("unsupported export format: {}", format)),
+    }
+}
+
+/// Remembers the folder of a successful export as the dialog's next starting point.
+pub(crate) fn remember_dir(app: &tauri::AppHandle, dir: &Path) {
+    if let Err(e) = settings::set(app, LAST_DIR_KEY, dir.to_string_lossy()) {
+        log::warn!("Failed to remember export directory: {}", e);
+    }
+}
+
+/// The last export folder if it still exists (shares get unmounted, folders deleted), else
+/// the user's documents folder.
+fn last_dir(app: &tauri::AppHandle) -> Option<PathBuf> {
+    settings::get::<String>(app, LAST_DIR_KEY)
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_dir())
+        .or_else(|| app.path().document_dir().ok())
+}
+
+async fn run_export(
+    app: tauri::AppHandle,
+    label: String,
+    path: PathBuf,
+    write: impl FnOnce() -> Result<(), String> + Send + 'static,
+) -> Result<(), String> {
+    let registry = app.state::<TaskRegistry>();
+    let _task = registry.start(&app, TaskKind::Export, label, false);
+    tauri::async_runtime::spawn_blocking(write)
+        .await
+        .map_err(|e| e.to_string())??;
+    if let Some(dir) = path.parent() {
+        remember_dir(&app, dir);
+    }
+    let _ = app.emit("export-complete", path.to_string_lossy());
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn export_pdf(
+    app: tauri::AppHandle,
+    html: String,
+    path: String,
+    metadata: Option<DocumentMetadata>,
+) -> Result<(), String> {
+    let metadata = ExportMetadata::new(&app, metadata);
+    let path = PathBuf::from(path);
+    let label = format!("PDF: {}", path.display());
+    run_export(app, label, path.clone(), move || pdf::write(&path, &html, &metadata)).await
+}
+
+#[tauri::command]
+pub async fn export_docx(
+    app: tauri::AppHandle,
+    html: String,
+    path: String,
+    metadata: Option<DocumentMetadata>,
+) -> Result<(), String> {
+    let metadata = ExportMetadata::new(&app, metadata);
+    let path = PathBuf::from(path);
+    let label = format!("DOCX: {}", path.display());
+    run_export(app, label, path.clone(), move || docx::write(&path, &html, &metadata)).await
+}
+
+/// Starting path for the export dialog; `None` only if not even a documents folder exists.
+#[tauri::command]
+pub async fn get_last_export_dir(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    Ok(last_dir(&app).map(|dir| dir.to_string_lossy().to_string()))
+}
+
+#[tauri::command]
+pub async fn set_last_export_dir(app: tauri::AppHandle, dir: String) -> Result<(), String> {
+    let dir = PathBuf::from(dir);
+    if !dir.is_dir() {
+        return Err(format!("not a directory: {}", dir.display()));
+    }
+    settings::set(&app, LAST_DIR_KEY, dir.to_string_lossy())
+}
@@ -0,0 +1,360 @@
+//! Plain PDF 1.4 writer using the standard Helvetica fonts with WinAnsi encoding, which
+//! covers German text (umlauts, ß, typographic quotes) without embedding a font file.
+
+use std::fs;
+use std::path::Path;
+
+use super::{write_error, ExportMetadata};
+use crate::html::{self, BlockKind};
+
+const PAGE_WIDTH: f64 = 595.0;
+const PAGE_HEIGHT: f64 = 842.0;
+const MARGIN: f64 = 56.0;
+
+const REGULAR: &str = "F1";
+const BOLD: &str = "F2";
+
+/// Helvetica advance widths for ASCII 32..=126, in 1/1000 em.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+struct Line {
+    font: &'static str,
+    size: f64,
+    x: f64,
+    y: f64,
+    text: String,
+}
+
+pub fn write(path: &Path, html: &str, metadata: &ExportMetadata) -> Result<(), String> {
+    let pages = layout(html);
+    fs::write(path, render(&pages, metadata)).map_err(|e| write_error(path, e))
+}
+
+fn layout(html: &str) -> Vec<Vec<Line>> {
+    let mut pages: Vec<Vec<Line>> = vec![Vec::new()];
+    let mut y = PAGE_HEIGHT - MARGIN;
+
+    for block in html::to_blocks(html) {
+        let (font, size, indent, space_before) = match block.kind {
+            BlockKind::Heading(1) => (BOLD, 18.0, 0.0, 14.0),
+            BlockKind::Heading(2) => (BOLD, 15.0, 0.0, 12.0),
+            BlockKind::Heading(_) => (BOLD, 12.5, 0.0, 10.0),
+            BlockKind::ListItem => (REGULAR, 11.0, 14.0, 3.0),
+            BlockKind::Paragraph => (REGULAR, 11.0, 0.0, 7.0),
+        };
+        let leading = size * 1.35;
+        let max_width = PAGE_WIDTH - 2.0 * MARGIN - indent;
+
+        y -= space_before;
+        for (i, text) in wrap(&block.text, font, size, max_width).into_iter().enumerate() {
+            if y - leading < MARGIN {
+                pages.push(Vec::new());
+                y = PAGE_HEIGHT - MARGIN;
+            }
+            y -= leading;
+            let page = pages.last_mut().expect("at least one page");
+            if i == 0 && block.kind == BlockKind::ListItem {
+                page.push(Line {
+                    font,
+                    size,
+                    x: MARGIN + 2.0,
+                    y,
+                    text: "•".to_string(),
+                });
+            }
+            page.push(Line {
+                font,
+                size,
+                x: MARGIN + indent,
+                y,
+                text,
+            });
+        }
+    }
+    pages
+}
+
+fn wrap(text: &str, font: &str, size: f64, max_width: f64) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", current, word)
+        };
+        if text_width(&candidate, font, size) <= max_width {
+            current = candidate;
+            continue;
+        }
+        if !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        // Words longer than a full line (URLs, hashtags) are broken by character.
+        for c in word.chars() {
+            current.push(c);
+            if text_width(&current, font, size) > max_width {
+                current.pop();
+                lines.push(std::mem::take(&mut current));
+                current.push(c);
+            }
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn text_width(text: &str, font: &str, size: f64) -> f64 {
+    let units: u32 = text.chars().map(char_width).sum();
+    let bold_factor = if font == BOLD { 1.06 } else { 1.0 };
+    units as f64 * size / 1000.0 * bold_factor
+}
+
+fn char_width(c: char) -> u32 {
+    match c {
+        ' '..='~' => HELVETICA_WIDTHS[c as usize - 32] as u32,
+        'Ä' => 667,
+        'Ö' => 778,
+        'Ü' => 722,
+        'ß' => 611,
+        '—' | '…' => 1000,
+        '„' | '“' | '”' | '‘' | '’' => 333,
+        '•' => 350,
+        _ => 556,
+    }
+}
+
+/// Encodes text for a WinAnsi content-stream string literal, escaping delimiters.
+fn encode_win_ansi(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        let byte = match c {
+            '(' | ')' | '\\' => {
+                bytes.push(b'\\');
+                c as u8
+            }
+            ' '..='~' => c as u8,
+            '\u{a0}'..='\u{ff}' => c as u32 as u8,
+            '€' => 0x80,
+            '‚' => 0x82,
+            '„' => 0x84,
+            '…' => 0x85,
+            '‘' => 0x91,
+            '’' => 0x92,
+            '“' => 0x93,
+            '”' => 0x94,
+            '•' => 0x95,
+            '–' => 0x96,
+            '—' => 0x97,
+            '™' => 0x99,
+            _ => b'?',
+        };
+        bytes.push(byte);
+    }
+    bytes
+}
+
+/// Document-information strings: literal when ASCII, otherwise UTF-16BE with BOM.
+fn info_string(text: &str) -> String {
+    if text.chars().all(|c| (' '..='~').contains(&c)) {
+        let escaped = text
+            .replace('\\', "\\\\")
+            .replace('(', "\\(")
+            .replace(')', "\\)");
+        return format!("({})", escaped);
+    }
+    let hex: String = text
+        .encode_utf16()
+        .map(|unit| format!("{:04X}", unit))
+        .collect();
+    format!("<FEFF{}>", hex)
+}
+
+fn render(pages: &[Vec<Line>], metadata: &ExportMetadata) -> Vec<u8> {
+    const CATALOG: usize = 1;
+    const PAGES: usize = 2;
+    const FONT_REGULAR: usize = 3;
+    const FONT_BOLD: usize = 4;
+    const INFO: usize = 5;
+    const FIRST_PAGE: usize = 6;
+
+    let mut objects: Vec<Vec<u8>> = Vec::new();
+
+    objects.push(format!("<< /Type /Catalog /Pages {} 0 R >>", PAGES).into_bytes());
+
+    let kids: Vec<String> = (0..pages.len())
+        .map(|i| format!("{} 0 R", FIRST_PAGE + i * 2))
+        .collect();
+    objects.push(
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        )
+        .into_bytes(),
+    );
+
+    for base_font in ["Helvetica", "Helvetica-Bold"] {
+        objects.push(
+            format!(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                base_font
+            )
+            .into_bytes(),
+        );
+    }
+
+    let mut info = format!(
+        "<< /Producer {} /Creator {} /CreationDate (D:{}Z)",
+        info_string(&metadata.producer()),
+        info_string(&metadata.app_name),
+        metadata.created_at.format("%Y%m%d%H%M%S")
+    );
+    if let Some(title) = &metadata.title {
+        info.push_str(&format!(" /Title {}", info_string(title)));
+    }
+    if let Some(author) = &metadata.author {
+        info.push_str(&format!(" /Author {}", info_string(author)));
+    }
+    info.push_str(" >>");
+    objects.push(info.into_bytes());
+
+    for (i, lines) in pages.iter().enumerate() {
+        let mut content = b"BT\n".to_vec();
+        for line in lines {
+            content.extend_from_slice(
+                format!(
+                    "/{} {} Tf\n1 0 0 1 {:.2} {:.2} Tm\n(",
+                    line.font, line.size, line.x, line.y
+                )
+                .as_bytes(),
+            );
+            content.extend_from_slice(&encode_win_ansi(&line.text));
+            content.extend_from_slice(b") Tj\n");
+        }
+        content.extend_from_slice(b"ET\n");
+
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /{} {} 0 R /{} {} 0 R >> >> /Contents {} 0 R >>",
+                PAGES, PAGE_WIDTH, PAGE_HEIGHT, REGULAR, FONT_REGULAR, BOLD, FONT_BOLD, FIRST_PAGE + i * 2 + 1
+            )
+            .into_bytes(),
+        );
+
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend_from_slice(&content);
+        stream.extend_from_slice(b"endstream");
+        objects.push(stream);
+    }
+
+    let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend_from_slice(format!("{} 0 obj\n", i + 1).as_bytes());
+        out.extend_from_slice(object);
+        out.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref_offset = out.len();
+    out.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+    for offset in offsets {
+        out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    out.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root {} 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            CATALOG,
+            INFO,
+            xref_offset
+        )
+        .as_bytes(),
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn position(haystack: &[u8], needle: &[u8]) -> usize {
+        haystack.windows(needle.len()).position(|window| window == needle).unwrap()
+    }
+
+    fn last_position(haystack: &[u8], needle: &[u8]) -> usize {
+        haystack.windows(needle.len()).rposition(|window| window == needle).unwrap()
+    }
+
+    fn number_at(bytes: &[u8], start: usize) -> usize {
+        let digits = bytes[start..].iter().take_while(|b| b.is_ascii_digit()).count();
+        std::str::from_utf8(&bytes[start..start + digits]).unwrap().parse().unwrap()
+    }
+
+    /// The Info dictionary, found the way a reader finds it: `startxref`, the trailer's `/Info`
+    /// reference, then the object's offset from the xref table.
+    fn info_dict(pdf: &[u8]) -> String {
+        let startxref = last_position(pdf, b"startxref\n");
+        let xref = number_at(pdf, startxref + "startxref\n".len());
+        assert!(pdf[xref..].starts_with(b"xref\n0 "));
+        let trailer = xref + position(&pdf[xref..], b"trailer");
+        let info = number_at(pdf, trailer + position(&pdf[trailer..], b"/Info ") + "/Info ".len());
+
+        let entries = xref + position(&pdf[xref..], b"65535 f \n") - "0000000000 ".len();
+        let offset = number_at(pdf, entries + info * 20);
+        let header = format!("{} 0 obj\n", info);
+        assert!(pdf[offset..].starts_with(header.as_bytes()));
+        let body = &pdf[offset + header.len()..];
+        String::from_utf8(body[..position(body, b"\nendobj")].to_vec()).unwrap()
+    }
+
+    /// Decodes the string value of `key` in `dict`, literal or UTF-16BE hex.
+    fn info_value(dict: &str, key: &str) -> String {
+        let start = dict.find(&format!("/{} ", key)).unwrap() + key.len() + 2;
+        let value = &dict[start..];
+        if let Some(hex) = value.strip_prefix("<FEFF") {
+            let hex = &hex[..hex.find('>').unwrap()];
+            let units: Vec<u16> = (0..hex.len())
+                .step_by(4)
+                .map(|i| u16::from_str_radix(&hex[i..i + 4], 16).unwrap())
+                .collect();
+            return String::from_utf16(&units).unwrap();
+        }
+        let literal = value.strip_prefix('(').unwrap();
+        literal[..literal.find(')').unwrap()].to_string()
+    }
+
+    #[test]
+    fn metadata_reads_back_from_the_info_dictionary() {
+        let metadata = ExportMetadata {
+            title: Some("Pressemitteilung: Grüne & Verkehr".to_string()),
+            author: Some("Kreisverband Köln".to_string()),
+            created_at: chrono::Utc.with_ymd_and_hms(2026, 3, 29, 8, 30, 0).unwrap(),
+            app_name: "Grünerator".to_string(),
+            app_version: "1.0.1".to_string(),
+        };
+        let path = std::env::temp_dir().join(format!("gruenerator-pdf-{}.pdf", std::process::id()));
+        write(&path, "<h1>Titel</h1><p>Straßenbahn für alle</p>", &metadata).unwrap();
+        let pdf = fs::read(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        let info = info_dict(&pdf);
+        assert_eq!(info_value(&info, "Title"), "Pressemitteilung: Grüne & Verkehr");
+        assert_eq!(info_value(&info, "Author"), "Kreisverband Köln");
+        assert_eq!(info_value(&info, "Producer"), "Grünerator 1.0.1");
+        assert_eq!(info_value(&info, "Creator"), "Grünerator");
+        assert_eq!(info_value(&info, "CreationDate"), "D:20260329083000Z");
+    }
+}
@@ -0,0 +1,153 @@
+//! Chunked export for very large documents: the frontend streams HTML in pieces instead of
+//! sending one huge string over IPC, and the chunks are spooled to a temp file until
+//! `export_finish` converts them.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+
+use super::{remember_dir, write_as, DocumentMetadata, ExportMetadata};
+use crate::paths;
+use crate::tasks::{TaskHandle, TaskKind, TaskRegistry};
+
+struct StreamingExport {
+    task: TaskHandle,
+    writer: BufWriter<File>,
+    spool: PathBuf,
+    written: u64,
+    expected: Option<u64>,
+}
+
+impl Drop for StreamingExport {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.spool);
+    }
+}
+
+#[derive(Default)]
+pub struct StreamingExports(Mutex<HashMap<String, StreamingExport>>);
+
+#[derive(Clone, Serialize)]
+struct ExportProgress {
+    task_id: String,
+    bytes_written: u64,
+    expected_bytes: Option<u64>,
+}
+
+#[tauri::command]
+pub async fn export_start(
+    app: tauri::AppHandle,
+    exports: tauri::State<'_, StreamingExports>,
+    expected_bytes: Option<u64>,
+) -> Result<String, String> {
+    let spool_dir = paths::cache_dir(&app)?.join("exports");
+    fs::create_dir_all(&spool_dir).map_err(|e| e.to_string())?;
+
+    let task = app
+        .state::<TaskRegistry>()
+        .start(&app, TaskKind::Export, "Export", true);
+    let task_id = task.id().to_string();
+    let spool = spool_dir.join(format!("{}.html", task_id));
+    let writer = BufWriter::new(File::create(&spool).map_err(|e| e.to_string())?);
+
+    let handle = app.clone();
+    let cancelled_id = task_id.clone();
+    task.on_cancel(move || {
+        handle
+            .state::<StreamingExports>()
+            .0
+            .lock()
+            .unwrap()
+            .remove(&cancelled_id);
+    });
+
+    exports.0.lock().unwrap().insert(
+        task_id.clone(),
+        StreamingExport {
+            task,
+            writer,
+            spool,
+            written: 0,
+            expected: expected_bytes,
+        },
+    );
+    Ok(task_id)
+}
+
+#[tauri::command]
+pub async fn export_append(
+    app: tauri::AppHandle,
+    exports: tauri::State<'_, StreamingExports>,
+    task_id: String,
+    chunk: String,
+) -> Result<(), String> {
+    let mut sessions = exports.0.lock().unwrap();
+    let export = sessions
+        .get_mut(&task_id)
+        .ok_or_else(|| format!("unknown export: {}", task_id))?;
+
+    if let Err(e) = export.writer.write_all(chunk.as_bytes()) {
+        sessions.remove(&task_id);
+        return Err(e.to_string());
+    }
+    export.written += chunk.len() as u64;
+    if let Some(expected) = export.expected.filter(|expected| *expected > 0) {
+        export.task.set_progress(export.written as f64 / expected as f64);
+    }
+
+    let _ = app.emit(
+        "export-progress",
+        ExportProgress {
+            task_id,
+            bytes_written: export.written,
+            expected_bytes: export.expected,
+        },
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn export_finish(
+    app: tauri::AppHandle,
+    exports: tauri::State<'_, StreamingExports>,
+    task_id: String,
+    path: String,
+    format: String,
+    metadata: Option<DocumentMetadata>,
+) -> Result<(), String> {
+    let mut export = exports
+        .0
+        .lock()
+        .unwrap()
+        .remove(&task_id)
+        .ok_or_else(|| format!("unknown export: {}", task_id))?;
+    export.writer.flush().map_err(|e| e.to_string())?;
+
+    let metadata = ExportMetadata::new(&app, metadata);
+    let target = PathBuf::from(path);
+    let dir = target.parent().map(PathBuf::from);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        match format.as_str() {
+            "html" => fs::copy(&export.spool, &target)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            _ => {
+                let html = fs::read_to_string(&export.spool).map_err(|e| e.to_string())?;
+                write_as(&format, &target, &html, &metadata)
+            }
+        }
+        // `export` drops here, removing the spool file and the task.
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    if let Some(dir) = dir {
+        remember_dir(&app, &dir);
+    }
+    Ok(())
+}
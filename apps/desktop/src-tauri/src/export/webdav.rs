@@ -0,0 +1,226 @@
+//! Uploads an export to a WebDAV server (typically the Nextcloud of a party office) instead of
+//! a local file. Server URL and user name are settings; the password lives in the OS keychain.
+//! Only HTTPS endpoints are accepted, as the password goes out with every request.
+//!
+//! Errors are codes the frontend can act on: `webdav_not_configured`, `webdav_insecure_url`,
+//! `webdav_auth_failed`, `webdav_folder_missing`, `webdav_quota_exceeded`,
+//! `webdav_connection_failed: …`, `webdav_server_error: <status>` and `cancelled`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use serde::Serialize;
+use serde_json::json;
+use tauri::{Emitter, Manager};
+use url::Url;
+
+use super::{write_as, ExportMetadata, FORMATS};
+use crate::paths;
+use crate::settings;
+use crate::tasks::{TaskKind, TaskRegistry};
+
+const URL_KEY: &str = "webdav_url";
+const USERNAME_KEY: &str = "webdav_username";
+const KEYCHAIN_SERVICE: &str = "de.gruenerator.desktop";
+const CHUNK_SIZE: usize = 64 * 1024;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+#[derive(Clone, Serialize)]
+pub struct WebdavConfig {
+    url: Option<String>,
+    username: Option<String>,
+    has_password: bool,
+}
+
+#[derive(Clone, Serialize)]
+pub struct WebdavUpload {
+    url: String,
+    bytes: u64,
+}
+
+fn keychain_entry(username: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, &format!("webdav:{}", username))
+        .map_err(|e| format!("keychain_unavailable: {}", e))
+}
+
+fn parse_server_url(url: &str) -> Result<Url, String> {
+    let url = Url::parse(url.trim()).map_err(|_| "invalid_url".to_string())?;
+    if url.scheme() != "https" {
+        return Err("webdav_insecure_url".to_string());
+    }
+    if url.cannot_be_a_base() || url.host_str().is_none() {
+        return Err("invalid_url".to_string());
+    }
+    Ok(url)
+}
+
+/// Appends `path_on_server` to the server URL segment by segment, so names are
+/// percent-encoded and can't climb out of the configured folder.
+fn target_url(base: &Url, path_on_server: &str, format: &str) -> Result<Url, String> {
+    let segments: Vec<&str> = path_on_server.split('/').filter(|segment| !segment.is_empty()).collect();
+    if segments.is_empty() || segments.iter().any(|segment| *segment == "." || *segment == "..") {
+        return Err("invalid_path".to_string());
+    }
+    let mut url = base.clone();
+    {
+        let mut path = url.path_segments_mut().map_err(|_| "invalid_url".to_string())?;
+        path.pop_if_empty().extend(&segments);
+    }
+    let extension = format!(".{}", format);
+    if !url.path().to_lowercase().ends_with(&extension) {
+        let path = format!("{}{}", url.path(), extension);
+        url.set_path(&path);
+    }
+    Ok(url)
+}
+
+fn content_type(format: &str) -> &'static str {
+    match format {
+        "pdf" => "application/pdf",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        _ => "text/html; charset=utf-8",
+    }
+}
+
+fn status_error(status: reqwest::StatusCode) -> String {
+    match status.as_u16() {
+        401 | 403 => "webdav_auth_failed".to_string(),
+        // 409 is what WebDAV answers when the parent collection doesn't exist.
+        404 | 409 => "webdav_folder_missing".to_string(),
+        507 => "webdav_quota_exceeded".to_string(),
+        code => format!("webdav_server_error: {}", code),
+    }
+}
+
+/// Renders the export through a scratch file, as the writers work on paths.
+fn render(app: &tauri::AppHandle, content: &str, format: &str, task_id: &str) -> Result<Vec<u8>, String> {
+    let dir = paths::cache_dir(app)?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("webdav-{}.{}", task_id, format));
+    let result = write_as(format, &path, content, &ExportMetadata::new(app, None))
+        .and_then(|()| std::fs::read(&path).map_err(|e| e.to_string()));
+    let _ = std::fs::remove_file(&path);
+    result
+}
+
+/// Stores the server and account; an empty `password` keeps the one in the keychain.
+#[tauri::command]
+pub async fn set_webdav_config(
+    app: tauri::AppHandle,
+    url: String,
+    username: String,
+    password: Option<String>,
+) -> Result<(), String> {
+    let url = parse_server_url(&url)?;
+    let username = username.trim().to_string();
+    if username.is_empty() {
+        return Err("username is required".to_string());
+    }
+    if let Some(password) = password.filter(|password| !password.is_empty()) {
+        keychain_entry(&username)?
+            .set_password(&password)
+            .map_err(|e| format!("keychain_unavailable: {}", e))?;
+    }
+    settings::set(&app, URL_KEY, url.as_str())?;
+    settings::set(&app, USERNAME_KEY, &username)
+}
+
+#[tauri::command]
+pub async fn get_webdav_config(app: tauri::AppHandle) -> Result<WebdavConfig, String> {
+    let username = settings::get::<String>(&app, USERNAME_KEY);
+    let has_password = username
+        .as_deref()
+        .and_then(|username| keychain_entry(username).ok())
+        .is_some_and(|entry| entry.get_password().is_ok());
+    Ok(WebdavConfig {
+        url: settings::get(&app, URL_KEY),
+        username,
+        has_password,
+    })
+}
+
+/// Uploads `content` (the rendered HTML) as `format` to `path_on_server`, relative to the
+/// configured server URL. Runs as a cancellable export task; `webdav-upload-progress` reports
+/// the bytes sent.
+#[tauri::command]
+pub async fn export_to_webdav(
+    app: tauri::AppHandle,
+    path_on_server: String,
+    content: String,
+    format: String,
+) -> Result<WebdavUpload, String> {
+    if !FORMATS.contains(&format.as_str()) {
+        return Err(format!("unsupported export format: {}", format));
+    }
+    let base = settings::get::<String>(&app, URL_KEY).ok_or_else(|| "webdav_not_configured".to_string())?;
+    let username =
+        settings::get::<String>(&app, USERNAME_KEY).ok_or_else(|| "webdav_not_configured".to_string())?;
+    let password = match keychain_entry(&username)?.get_password() {
+        Ok(password) => password,
+        Err(keyring::Error::NoEntry) => return Err("webdav_not_configured".to_string()),
+        Err(e) => return Err(format!("keychain_unavailable: {}", e)),
+    };
+    let url = target_url(&parse_server_url(&base)?, &path_on_server, &format)?;
+
+    let task = Arc::new(app.state::<TaskRegistry>().start(
+        &app,
+        TaskKind::Export,
+        format!("WebDAV: {}", path_on_server),
+        true,
+    ));
+    let bytes = {
+        let (app, task_id, format) = (app.clone(), task.id().to_string(), format.clone());
+        tauri::async_runtime::spawn_blocking(move || render(&app, &content, &format, &task_id))
+            .await
+            .map_err(|e| e.to_string())??
+    };
+    if task.is_cancelled() {
+        return Err("cancelled".to_string());
+    }
+
+    let total = bytes.len() as u64;
+    let chunks: Vec<Vec<u8>> = bytes.chunks(CHUNK_SIZE).map(<[u8]>::to_vec).collect();
+    let progress = {
+        let (app, task) = (app.clone(), task.clone());
+        let mut sent = 0u64;
+        futures_util::stream::iter(chunks).map(move |chunk| {
+            sent += chunk.len() as u64;
+            task.set_progress(sent as f64 / total as f64);
+            let _ = app.emit(
+                "webdav-upload-progress",
+                json!({ "task_id": task.id(), "sent": sent, "total": total }),
+            );
+            Ok::<_, std::io::Error>(chunk)
+        })
+    };
+
+    let client = reqwest::Client::builder()
+        .https_only(true)
+        .connect_timeout(CONNECT_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let request = client
+        .put(url.clone())
+        .basic_auth(&username, Some(&password))
+        .header(reqwest::header::CONTENT_TYPE, content_type(&format))
+        .header(reqwest::header::CONTENT_LENGTH, total)
+        .body(reqwest::Body::wrap_stream(progress));
+
+    let upload = tauri::async_runtime::spawn(request.send());
+    let abort = upload.inner().abort_handle();
+    task.on_cancel(move || abort.abort());
+    let response = match upload.await {
+        Ok(response) => response.map_err(|e| format!("webdav_connection_failed: {}", e))?,
+        Err(_) if task.is_cancelled() => return Err("cancelled".to_string()),
+        Err(e) => return Err(e.to_string()),
+    };
+    if !response.status().is_success() {
+        log::warn!("WebDAV upload to {} failed: {}", url, response.status());
+        return Err(status_error(response.status()));
+    }
+    Ok(WebdavUpload {
+        url: url.to_string(),
+        bytes: total,
+    })
+}
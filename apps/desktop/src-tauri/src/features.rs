@@ -0,0 +1,75 @@
+//! Feature flags for beta functionality. Only flags listed in [`FLAGS`] exist; the store
+//! holds just the ones that differ from their default.
+
+use std::collections::{BTreeMap, HashMap};
+
+use tauri::Emitter;
+
+use crate::{accessibility, settings};
+
+const FLAGS_KEY: &str = "feature_flags";
+
+struct FeatureFlag {
+    name: &'static str,
+    default: bool,
+}
+
+const FLAGS: &[FeatureFlag] = &[
+    FeatureFlag { name: "streaming_export", default: false },
+    FeatureFlag { name: "vibrancy", default: false },
+    FeatureFlag { name: "multi_window", default: false },
+    FeatureFlag { name: accessibility::FEATURE_FLAG, default: false },
+];
+
+fn overrides(app: &tauri::AppHandle) -> HashMap<String, bool> {
+    settings::get(app, FLAGS_KEY).unwrap_or_default()
+}
+
+pub fn flags(app: &tauri::AppHandle) -> BTreeMap<String, bool> {
+    let overrides = overrides(app);
+    FLAGS
+        .iter()
+        .map(|flag| {
+            let enabled = overrides.get(flag.name).copied().unwrap_or(flag.default);
+            (flag.name.to_string(), enabled)
+        })
+        .collect()
+}
+
+pub fn is_enabled(app: &tauri::AppHandle, name: &str) -> bool {
+    flags(app).get(name).copied().unwrap_or(false)
+}
+
+fn save(app: &tauri::AppHandle, overrides: HashMap<String, bool>) -> Result<(), String> {
+    settings::set(app, FLAGS_KEY, overrides)?;
+    let _ = app.emit("feature-flags-changed", flags(app));
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_feature_flags(app: tauri::AppHandle) -> Result<BTreeMap<String, bool>, String> {
+    Ok(flags(&app))
+}
+
+#[tauri::command]
+pub async fn set_feature_flag(app: tauri::AppHandle, name: String, enabled: bool) -> Result<(), String> {
+    let flag = FLAGS
+        .iter()
+        .find(|flag| flag.name == name)
+        .ok_or_else(|| format!("unknown feature flag: {}", name))?;
+
+    let mut overrides = overrides(&app);
+    // Drop names that are no longer defined so the store doesn't accumulate them.
+    overrides.retain(|key, _| FLAGS.iter().any(|flag| flag.name == key));
+    if enabled == flag.default {
+        overrides.remove(&name);
+    } else {
+        overrides.insert(name, enabled);
+    }
+    save(&app, overrides)
+}
+
+#[tauri::command]
+pub async fn reset_feature_flags(app: tauri::AppHandle) -> Result<(), String> {
+    save(&app, HashMap::new())
+}
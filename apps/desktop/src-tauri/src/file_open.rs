@@ -0,0 +1,76 @@
+//! Opening saved `.gruenerator` files from the file manager. Windows and Linux pass the file as
+//! a launch argument (to a second instance, the single-instance plugin forwards it); macOS
+//! delivers it as `RunEvent::Opened`. Either way the frontend gets `open-document` with
+//! `{ path }` (deep links to a saved document carry `{ doc, section }` instead); files opened
+//! during startup are buffered until it is ready.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+use tauri::Manager;
+
+use crate::startup;
+
+pub const EXTENSION: &str = "gruenerator";
+
+fn has_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case(EXTENSION))
+}
+
+/// Announces `path` to the frontend and, if the app was already running, brings it forward.
+/// Anything but an existing `.gruenerator` file is rejected with `open-document-rejected`.
+pub fn open(app: &tauri::AppHandle, path: &Path) {
+    let reason = if !has_extension(path) {
+        Some("unsupported_file_type")
+    } else if !path.is_file() {
+        Some("not_found")
+    } else {
+        None
+    };
+    if let Some(reason) = reason {
+        log::warn!("Not opening {}: {}", path.display(), reason);
+        startup::notify(
+            app,
+            "open-document-rejected",
+            json!({ "path": path.to_string_lossy(), "reason": reason }),
+        );
+        return;
+    }
+
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    log::info!("Opening document {}", path.display());
+    // On a cold start the splash reveals the window once it has loaded.
+    if startup::is_marked(app, startup::MAIN_SHOWN) {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.show();
+            let _ = window.unminimize();
+            let _ = window.set_focus();
+        }
+    }
+    startup::notify(app, "open-document", json!({ "path": path.to_string_lossy() }));
+}
+
+/// Opens the files among `args`, skipping the executable and flags. Other existing files
+/// (e.g. from "Öffnen mit") count as opens and are rejected.
+pub fn handle_args(app: &tauri::AppHandle, args: &[String]) {
+    for arg in args.iter().skip(1).filter(|arg| !arg.starts_with('-')) {
+        let path = PathBuf::from(arg);
+        if has_extension(&path) || path.is_file() {
+            open(app, &path);
+        }
+    }
+}
+
+/// The file URLs of a macOS `RunEvent::Opened`; other schemes are deep links, which the
+/// deep-link plugin handles.
+#[cfg(target_os = "macos")]
+pub fn handle_urls(app: &tauri::AppHandle, urls: &[url::Url]) {
+    for url in urls.iter().filter(|url| url.scheme() == "file") {
+        match url.to_file_path() {
+            Ok(path) => open(app, &path),
+            Err(()) => log::warn!("Ignoring unusable file URL: {}", url),
+        }
+    }
+}
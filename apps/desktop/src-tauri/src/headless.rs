@@ -0,0 +1,153 @@
+//! `--headless --prompt "…" --out file.md`: a single generation without any window, for
+//! scripts and CI. Detected on the raw process args, before the regular app is built.
+//!
+//! The generation goes through the web frontend's API client, which holds the user's session:
+//! a reduced app loads the main window without ever showing it, the frontend fetches the
+//! prompt with `get_headless_request` once it has mounted, and answers with
+//! `submit_headless_result`.
+//! Errors go to stderr; the exit status is one of the `EXIT_*` codes.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tauri::Manager;
+
+const HEADLESS_ARG: &str = "--headless";
+const PROMPT_ARG: &str = "--prompt";
+const OUT_ARG: &str = "--out";
+const TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+const EXIT_OK: i32 = 0;
+const EXIT_GENERATION_FAILED: i32 = 1;
+const EXIT_USAGE: i32 = 2;
+const EXIT_TIMEOUT: i32 = 3;
+const EXIT_WRITE_FAILED: i32 = 4;
+
+pub struct HeadlessRequest {
+    prompt: String,
+    out: PathBuf,
+}
+
+struct HeadlessJob {
+    request: HeadlessRequest,
+    started: AtomicBool,
+    finished: AtomicBool,
+}
+
+fn usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("Usage: gruenerator --headless --prompt \"…\" --out file.md");
+    std::process::exit(EXIT_USAGE);
+}
+
+/// The headless request, if `--headless` was passed. Exits with `EXIT_USAGE` when the other
+/// arguments are missing.
+pub fn requested() -> Option<HeadlessRequest> {
+    let args: Vec<String> = std::env::args().collect();
+    if !args.iter().any(|arg| arg == HEADLESS_ARG) {
+        return None;
+    }
+    let value = |name: &str| {
+        args.iter()
+            .position(|arg| arg == name)
+            .and_then(|i| args.get(i + 1))
+            .filter(|value| !value.starts_with("--"))
+            .cloned()
+    };
+    let prompt = value(PROMPT_ARG)
+        .filter(|prompt| !prompt.trim().is_empty())
+        .unwrap_or_else(|| usage("Missing --prompt"));
+    let out = value(OUT_ARG).map(PathBuf::from).unwrap_or_else(|| usage("Missing --out"));
+    Some(HeadlessRequest { prompt, out })
+}
+
+/// Release builds on Windows have no console of their own; borrow the calling terminal's so
+/// stderr reaches it.
+fn attach_console() {
+    #[cfg(windows)]
+    unsafe {
+        use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+fn finish(app: &tauri::AppHandle, code: i32) {
+    let job = app.state::<HeadlessJob>();
+    if !job.finished.swap(true, Ordering::SeqCst) {
+        app.exit(code);
+    }
+}
+
+/// Called by the frontend once it has mounted: `{ prompt }` of the headless run, or `None` in
+/// a regular launch. Handed out only once, so a reload doesn't start a second generation.
+#[tauri::command]
+pub async fn get_headless_request(app: tauri::AppHandle) -> Result<Option<Value>, String> {
+    let Some(job) = app.try_state::<HeadlessJob>() else {
+        return Ok(None);
+    };
+    if job.started.swap(true, Ordering::SeqCst) {
+        return Ok(None);
+    }
+    Ok(Some(json!({ "prompt": job.request.prompt })))
+}
+
+/// The frontend's answer to `get_headless_request`: the generated text, or why there is none.
+#[tauri::command]
+async fn submit_headless_result(
+    app: tauri::AppHandle,
+    text: Option<String>,
+    error: Option<String>,
+) -> Result<(), String> {
+    let code = match (text, error) {
+        (Some(text), None) => {
+            let out = &app.state::<HeadlessJob>().request.out;
+            match std::fs::write(out, text) {
+                Ok(()) => EXIT_OK,
+                Err(e) => {
+                    eprintln!("Failed to write {}: {}", out.display(), e);
+                    EXIT_WRITE_FAILED
+                }
+            }
+        }
+        (_, error) => {
+            eprintln!("Generation failed: {}", error.as_deref().unwrap_or("no result"));
+            EXIT_GENERATION_FAILED
+        }
+    };
+    finish(&app, code);
+    Ok(())
+}
+
+/// Runs the generation and exits; never returns to the regular startup.
+pub fn run(request: HeadlessRequest) -> ! {
+    attach_console();
+    let app = tauri::Builder::default()
+        .plugin(tauri_plugin_store::Builder::new().build())
+        .manage(HeadlessJob {
+            request,
+            started: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+        })
+        .invoke_handler(tauri::generate_handler![get_headless_request, submit_headless_result])
+        .setup(|app| {
+            let app = app.handle().clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(TIMEOUT);
+                if !app.state::<HeadlessJob>().finished.load(Ordering::SeqCst) {
+                    eprintln!("Generation timed out after {} s", TIMEOUT.as_secs());
+                    finish(&app, EXIT_TIMEOUT);
+                }
+            });
+            Ok(())
+        })
+        .build(tauri::generate_context!());
+    match app {
+        Ok(app) => std::process::exit(app.run_return(|_, _| {})),
+        Err(e) => {
+            eprintln!("Failed to start: {}", e);
+            std::process::exit(EXIT_GENERATION_FAILED);
+        }
+    }
+}
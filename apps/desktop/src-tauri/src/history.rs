@@ -0,0 +1,138 @@
+//! Local version history: periodic, gzip-compressed content snapshots per document that
+//! survive restarts. Separate from drafts, which only hold the latest state for crash
+//! recovery. Snapshot files are named `<millis>-<hash>.gz`, so listing needs no index and an
+//! unchanged document doesn't produce a new snapshot.
+
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{TimeZone, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Serialize;
+
+use crate::{documents, paths};
+
+const HISTORY_DIR: &str = "history";
+const MAX_SNAPSHOTS: usize = 50;
+const HASH_CHARS: usize = 16;
+
+#[derive(Clone, Serialize)]
+pub struct HistorySnapshot {
+    id: String,
+    created_at: String,
+    compressed_size: u64,
+}
+
+struct SnapshotFile {
+    id: String,
+    millis: u64,
+    hash: String,
+    path: PathBuf,
+}
+
+fn history_dir(app: &tauri::AppHandle, doc_id: &str) -> Result<PathBuf, String> {
+    documents::validate_id(doc_id)?;
+    Ok(paths::data_dir(app)?.join(HISTORY_DIR).join(doc_id))
+}
+
+/// Snapshot files in `dir`, newest first.
+fn snapshot_files(dir: &Path) -> Vec<SnapshotFile> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<SnapshotFile> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let id = path.file_name()?.to_str()?.strip_suffix(".gz")?.to_string();
+            let (millis, hash) = id.split_once('-')?;
+            Some(SnapshotFile {
+                millis: millis.parse().ok()?,
+                hash: hash.to_string(),
+                id: id.clone(),
+                path,
+            })
+        })
+        .collect();
+    files.sort_by_key(|file| std::cmp::Reverse(file.millis));
+    files
+}
+
+#[tauri::command]
+pub async fn save_history_snapshot(
+    app: tauri::AppHandle,
+    doc_id: String,
+    state: String,
+) -> Result<Option<String>, String> {
+    let dir = history_dir(&app, &doc_id)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let hash = documents::hash_content(&state)[..HASH_CHARS].to_string();
+        let existing = snapshot_files(&dir);
+        if existing.first().is_some_and(|latest| latest.hash == hash) {
+            return Ok(None);
+        }
+
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let mut millis = crate::now_millis();
+        // Two snapshots within the same millisecond would otherwise share a name.
+        if let Some(latest) = existing.first() {
+            millis = millis.max(latest.millis + 1);
+        }
+        let id = format!("{}-{}", millis, hash);
+        let mut encoder = GzEncoder::new(
+            File::create(dir.join(format!("{}.gz", id))).map_err(|e| e.to_string())?,
+            Compression::default(),
+        );
+        encoder.write_all(state.as_bytes()).map_err(|e| e.to_string())?;
+        encoder.finish().map_err(|e| e.to_string())?;
+
+        for stale in existing.iter().skip(MAX_SNAPSHOTS - 1) {
+            let _ = fs::remove_file(&stale.path);
+        }
+        Ok(Some(id))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Newest first.
+#[tauri::command]
+pub async fn list_history(app: tauri::AppHandle, doc_id: String) -> Result<Vec<HistorySnapshot>, String> {
+    let dir = history_dir(&app, &doc_id)?;
+    Ok(snapshot_files(&dir)
+        .into_iter()
+        .map(|file| HistorySnapshot {
+            created_at: Utc
+                .timestamp_millis_opt(file.millis as i64)
+                .single()
+                .map(|time| time.to_rfc3339())
+                .unwrap_or_default(),
+            compressed_size: fs::metadata(&file.path).map(|m| m.len()).unwrap_or(0),
+            id: file.id,
+        })
+        .collect())
+}
+
+/// Returns the snapshot's content; the frontend decides whether to apply and save it.
+#[tauri::command]
+pub async fn restore_history(
+    app: tauri::AppHandle,
+    doc_id: String,
+    snapshot_id: String,
+) -> Result<String, String> {
+    let dir = history_dir(&app, &doc_id)?;
+    let file = snapshot_files(&dir)
+        .into_iter()
+        .find(|file| file.id == snapshot_id)
+        .ok_or_else(|| format!("unknown snapshot: {}", snapshot_id))?;
+
+    let mut state = String::new();
+    GzDecoder::new(File::open(&file.path).map_err(|e| e.to_string())?)
+        .read_to_string(&mut state)
+        .map_err(|e| e.to_string())?;
+    Ok(state)
+}
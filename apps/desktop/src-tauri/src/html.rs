@@ -0,0 +1,159 @@
+//! Minimal HTML-to-text flattening shared by the native renderers (thumbnails, exports).
+//! It only needs to understand block structure, not styling.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockKind {
+    Heading(u8),
+    Paragraph,
+    ListItem,
+}
+
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub kind: BlockKind,
+    pub text: String,
+}
+
+const SKIPPED_TAGS: &[&str] = &["script", "style", "head", "title", "template", "noscript"];
+const BLOCK_TAGS: &[&str] = &[
+    "p", "div", "br", "section", "article", "blockquote", "tr", "ul", "ol", "table", "pre", "hr",
+    "header", "footer",
+];
+
+pub fn to_blocks(html: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut current = String::new();
+    let mut kind = BlockKind::Paragraph;
+    let mut skip_depth = 0usize;
+    let mut chars = html.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '<' => {
+                let mut tag = String::new();
+                for t in chars.by_ref() {
+                    if t == '>' {
+                        break;
+                    }
+                    tag.push(t);
+                }
+                let closing = tag.starts_with('/');
+                let name: String = tag
+                    .trim_start_matches('/')
+                    .chars()
+                    .take_while(|ch| ch.is_ascii_alphanumeric())
+                    .collect::<String>()
+                    .to_ascii_lowercase();
+
+                if SKIPPED_TAGS.contains(&name.as_str()) {
+                    if closing {
+                        skip_depth = skip_depth.saturating_sub(1);
+                    } else if !tag.ends_with('/') {
+                        skip_depth += 1;
+                    }
+                    continue;
+                }
+                if skip_depth > 0 {
+                    continue;
+                }
+
+                let heading = name
+                    .strip_prefix('h')
+                    .and_then(|level| level.parse::<u8>().ok())
+                    .filter(|level| (1..=6).contains(level));
+
+                if heading.is_some() || name == "li" || BLOCK_TAGS.contains(&name.as_str()) {
+                    flush(&mut blocks, &mut current, kind);
+                    kind = match (closing, heading, name.as_str()) {
+                        (false, Some(level), _) => BlockKind::Heading(level),
+                        (false, None, "li") => BlockKind::ListItem,
+                        _ => BlockKind::Paragraph,
+                    };
+                }
+            }
+            '&' if skip_depth == 0 => {
+                let mut entity = String::new();
+                while let Some(&next) = chars.peek() {
+                    if next == ';' || entity.len() > 8 {
+                        break;
+                    }
+                    if !(next.is_ascii_alphanumeric() || next == '#') {
+                        break;
+                    }
+                    entity.push(next);
+                    chars.next();
+                }
+                if chars.peek() == Some(&';') {
+                    chars.next();
+                    match decode_entity(&entity) {
+                        Some(decoded) => current.push(decoded),
+                        None => {
+                            current.push('&');
+                            current.push_str(&entity);
+                            current.push(';');
+                        }
+                    }
+                } else {
+                    current.push('&');
+                    current.push_str(&entity);
+                }
+            }
+            _ if skip_depth == 0 => {
+                if c.is_whitespace() {
+                    if !current.is_empty() && !current.ends_with(' ') {
+                        current.push(' ');
+                    }
+                } else {
+                    current.push(c);
+                }
+            }
+            _ => {}
+        }
+    }
+    flush(&mut blocks, &mut current, kind);
+    blocks
+}
+
+fn flush(blocks: &mut Vec<Block>, current: &mut String, kind: BlockKind) {
+    let text = current.trim();
+    if !text.is_empty() {
+        blocks.push(Block {
+            kind,
+            text: text.to_string(),
+        });
+    }
+    current.clear();
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    if let Some(code) = entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+        return u32::from_str_radix(code, 16).ok().and_then(char::from_u32);
+    }
+    if let Some(code) = entity.strip_prefix('#') {
+        return code.parse::<u32>().ok().and_then(char::from_u32);
+    }
+    let c = match entity {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => ' ',
+        "auml" => 'ä',
+        "ouml" => 'ö',
+        "uuml" => 'ü',
+        "Auml" => 'Ä',
+        "Ouml" => 'Ö',
+        "Uuml" => 'Ü',
+        "szlig" => 'ß',
+        "euro" => '€',
+        "ndash" => '–',
+        "mdash" => '—',
+        "bdquo" => '„',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "hellip" => '…',
+        _ => return None,
+    };
+    Some(c)
+}
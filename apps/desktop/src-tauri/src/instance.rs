@@ -0,0 +1,139 @@
+//! Detects a single-instance lock held by an instance that no longer responds, typically one
+//! that hung while crashing: the lock is still taken, so every new launch hands over to it
+//! and exits, and the user sees nothing until they reboot.
+//!
+//! The running instance records its pid and a heartbeat from the main thread in a small file.
+//! Before the single-instance plugin gets to defer to it, a new launch checks that process: if
+//! it exists but its heartbeat has stopped, and doesn't resume within `LIVENESS_GRACE`, the
+//! lock is considered stale. A healthy instance that is merely busy beats within that time and
+//! keeps the lock.
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+use crate::startup;
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(2);
+/// A heartbeat older than this makes the instance suspect.
+const HEARTBEAT_STALE: Duration = Duration::from_secs(6);
+/// How long a suspect instance gets to prove it's alive.
+const LIVENESS_GRACE: Duration = Duration::from_secs(3);
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct InstanceRecord {
+    pid: u32,
+    /// Process start in seconds since the epoch, to tell a reused pid from the instance.
+    started_at: u64,
+    heartbeat_ms: u64,
+}
+
+/// Outside the app data dir, which isn't known before the app is built; per user and per app
+/// `identifier` (staging builds have their own), as the lock itself is.
+fn record_path(identifier: &str) -> PathBuf {
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "default".to_string());
+    std::env::temp_dir().join(format!("{}-{}.instance", identifier, user))
+}
+
+fn read(identifier: &str) -> Option<InstanceRecord> {
+    let content = std::fs::read_to_string(record_path(identifier)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn write(identifier: &str, record: &InstanceRecord) -> std::io::Result<()> {
+    let path = record_path(identifier);
+    let partial = path.with_extension("instance.tmp");
+    std::fs::write(&partial, serde_json::to_vec(record)?)?;
+    std::fs::rename(partial, path)
+}
+
+fn process_start(pid: u32) -> Option<u64> {
+    let pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), true, ProcessRefreshKind::nothing());
+    system.process(pid).map(|process| process.start_time())
+}
+
+fn is_running(record: &InstanceRecord) -> bool {
+    process_start(record.pid).is_some_and(|started_at| started_at.abs_diff(record.started_at) <= 2)
+}
+
+/// The pid of a previous instance that is still running but has stopped responding. Waits up
+/// to `LIVENESS_GRACE` for a suspect instance before deciding.
+pub fn find_stale(identifier: &str) -> Option<u32> {
+    let record = read(identifier)?;
+    if record.pid == std::process::id() || !is_running(&record) {
+        return None;
+    }
+    let stale_ms = HEARTBEAT_STALE.as_millis() as u64;
+    if crate::now_millis().saturating_sub(record.heartbeat_ms) < stale_ms {
+        return None;
+    }
+
+    let deadline = Instant::now() + LIVENESS_GRACE;
+    while Instant::now() < deadline {
+        std::thread::sleep(Duration::from_millis(250));
+        match read(identifier) {
+            Some(current) if current.pid == record.pid && current.heartbeat_ms == record.heartbeat_ms => {}
+            // It beat, exited, or another instance took over: not ours to clear.
+            _ => return None,
+        }
+    }
+    log::warn!("Instance {} holds the single-instance lock but doesn't respond", record.pid);
+    Some(record.pid)
+}
+
+/// Makes way for this launch and returns whether it can still take the single-instance lock.
+/// On macOS the lock is a socket file that can be removed. The Windows mutex and the Linux
+/// D-Bus name belong to the hung process until it ends, so this launch runs without the lock,
+/// as in safe mode.
+pub fn clear_stale_lock(identifier: &str) -> bool {
+    if cfg!(target_os = "macos") {
+        let socket = format!("/tmp/{}_si.sock", identifier.replace(['.', '-'], "_"));
+        let _ = std::fs::remove_file(socket);
+        true
+    } else {
+        false
+    }
+}
+
+/// Records this instance and keeps its heartbeat going. The beat is written from the main
+/// thread, so a stuck event loop stops it. Emits `stale-lock-cleared` if this launch replaced
+/// an unresponsive instance.
+pub fn start_heartbeat(app: &tauri::AppHandle, replaced: Option<u32>) {
+    if let Some(pid) = replaced {
+        startup::notify(app, "stale-lock-cleared", json!({ "pid": pid }));
+    }
+
+    let Some(started_at) = process_start(std::process::id()) else { return };
+    let record = InstanceRecord {
+        pid: std::process::id(),
+        started_at,
+        heartbeat_ms: crate::now_millis(),
+    };
+    let identifier = app.config().identifier.clone();
+    if let Err(e) = write(&identifier, &record) {
+        log::warn!("Failed to record instance: {}", e);
+        return;
+    }
+
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(HEARTBEAT_INTERVAL);
+        let identifier = identifier.clone();
+        let result = app.run_on_main_thread(move || {
+            let _ = write(&identifier, &InstanceRecord {
+                heartbeat_ms: crate::now_millis(),
+                ..record
+            });
+        });
+        if result.is_err() {
+            break;
+        }
+    });
+}
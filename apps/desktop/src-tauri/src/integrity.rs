@@ -0,0 +1,172 @@
+//! Checks the installed app against what was built by verifying the executable's OS code
+//! signature where the platform has one (Authenticode, `codesign`; on macOS this covers the
+//! whole bundle). Unsigned builds report `unsigned` rather than a mismatch; only a signature
+//! that no longer matches counts. A report is therefore `ok` (nothing wrong found) but not
+//! `verified` when there was no signature to check, e.g. on Linux or for unsigned dev builds.
+//! The app bundles no separate resource files, so there is nothing else to hash.
+//!
+//! Unlike the updater's signature check, this looks at files that are already installed, e.g.
+//! to spot an update that was only partially applied or an executable that was tampered with.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::{settings, startup};
+
+const VERIFY_ON_STARTUP_KEY: &str = "verify_integrity_on_startup";
+
+#[derive(Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureStatus {
+    Valid,
+    Unsigned,
+    Invalid,
+    Unsupported,
+}
+
+#[derive(Clone, Serialize)]
+pub struct Mismatch {
+    path: String,
+    /// `invalid_signature`.
+    reason: &'static str,
+}
+
+#[derive(Clone, Serialize)]
+pub struct IntegrityReport {
+    /// No mismatch was found.
+    ok: bool,
+    /// A valid signature was checked; `false` means `ok` vouches for nothing.
+    verified: bool,
+    signature: SignatureStatus,
+    mismatches: Vec<Mismatch>,
+}
+
+#[cfg(windows)]
+fn check_signature(exe: &Path) -> SignatureStatus {
+    use windows::core::{GUID, HSTRING, PCWSTR};
+    use windows::Win32::Foundation::{HWND, TRUST_E_NOSIGNATURE};
+    use windows::Win32::Security::WinTrust::{
+        WinVerifyTrust, WINTRUST_ACTION_GENERIC_VERIFY_V2, WINTRUST_DATA, WINTRUST_DATA_0,
+        WINTRUST_FILE_INFO, WTD_CHOICE_FILE, WTD_REVOKE_NONE, WTD_STATEACTION_CLOSE,
+        WTD_STATEACTION_VERIFY, WTD_UI_NONE,
+    };
+
+    let path = HSTRING::from(exe.as_os_str());
+    let mut file = WINTRUST_FILE_INFO {
+        cbStruct: std::mem::size_of::<WINTRUST_FILE_INFO>() as u32,
+        pcwszFilePath: PCWSTR(path.as_ptr()),
+        ..Default::default()
+    };
+    let mut data = WINTRUST_DATA {
+        cbStruct: std::mem::size_of::<WINTRUST_DATA>() as u32,
+        dwUIChoice: WTD_UI_NONE,
+        // Offline installs must not fail on an unreachable CRL.
+        fdwRevocationChecks: WTD_REVOKE_NONE,
+        dwUnionChoice: WTD_CHOICE_FILE,
+        Anonymous: WINTRUST_DATA_0 { pFile: &mut file },
+        dwStateAction: WTD_STATEACTION_VERIFY,
+        ..Default::default()
+    };
+    let mut action: GUID = WINTRUST_ACTION_GENERIC_VERIFY_V2;
+    let data_ptr = &mut data as *mut WINTRUST_DATA as *mut std::ffi::c_void;
+    let status = unsafe { WinVerifyTrust(HWND::default(), &mut action, data_ptr) };
+    data.dwStateAction = WTD_STATEACTION_CLOSE;
+    let data_ptr = &mut data as *mut WINTRUST_DATA as *mut std::ffi::c_void;
+    unsafe { WinVerifyTrust(HWND::default(), &mut action, data_ptr) };
+
+    match status {
+        0 => SignatureStatus::Valid,
+        status if status == TRUST_E_NOSIGNATURE.0 => SignatureStatus::Unsigned,
+        _ => SignatureStatus::Invalid,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn check_signature(exe: &Path) -> SignatureStatus {
+    // Verify the whole .app (Contents/MacOS/<exe>), so resources and frameworks are covered.
+    let Some(bundle) = exe.ancestors().nth(3).filter(|dir| dir.extension().is_some_and(|ext| ext == "app")) else {
+        return SignatureStatus::Unsupported;
+    };
+    match std::process::Command::new("codesign")
+        .args(["--verify", "--deep", "--strict"])
+        .arg(bundle)
+        .output()
+    {
+        Ok(output) if output.status.success() => SignatureStatus::Valid,
+        Ok(output) if String::from_utf8_lossy(&output.stderr).contains("not signed at all") => {
+            SignatureStatus::Unsigned
+        }
+        Ok(_) => SignatureStatus::Invalid,
+        Err(e) => {
+            log::warn!("codesign unavailable: {}", e);
+            SignatureStatus::Unsupported
+        }
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn check_signature(_exe: &Path) -> SignatureStatus {
+    SignatureStatus::Unsupported
+}
+
+pub fn verify() -> IntegrityReport {
+    let mut mismatches = Vec::new();
+
+    let signature = match std::env::current_exe() {
+        Ok(exe) => {
+            let signature = check_signature(&exe);
+            if signature == SignatureStatus::Invalid {
+                mismatches.push(Mismatch {
+                    path: exe.to_string_lossy().into_owned(),
+                    reason: "invalid_signature",
+                });
+            }
+            signature
+        }
+        Err(e) => {
+            log::warn!("Executable path unavailable for integrity check: {}", e);
+            SignatureStatus::Unsupported
+        }
+    };
+
+    IntegrityReport {
+        ok: mismatches.is_empty(),
+        verified: signature == SignatureStatus::Valid,
+        signature,
+        mismatches,
+    }
+}
+
+/// Runs [`verify`] in the background when enabled in the settings and reports failures as
+/// `install-integrity-failed`.
+pub fn verify_on_startup(app: &tauri::AppHandle) {
+    if !settings::get(app, VERIFY_ON_STARTUP_KEY).unwrap_or(false) {
+        return;
+    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let report = verify();
+        if !report.ok {
+            for mismatch in &report.mismatches {
+                log::warn!("Integrity check failed for {}: {}", mismatch.path, mismatch.reason);
+            }
+            startup::notify(&app, "install-integrity-failed", report);
+        }
+    });
+}
+
+#[tauri::command]
+pub async fn verify_install_integrity() -> Result<IntegrityReport, String> {
+    Ok(verify())
+}
+
+#[tauri::command]
+pub async fn set_verify_integrity_on_startup(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    settings::set(&app, VERIFY_ON_STARTUP_KEY, enabled)
+}
+
+#[tauri::command]
+pub async fn get_verify_integrity_on_startup(app: tauri::AppHandle) -> Result<bool, String> {
+    Ok(settings::get(&app, VERIFY_ON_STARTUP_KEY).unwrap_or(false))
+}
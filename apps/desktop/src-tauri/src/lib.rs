@@ -1,21 +1,75 @@
-use tauri::{Emitter, Manager, Theme};
-use tauri::menu::{Menu, MenuItem, Submenu, PredefinedMenuItem};
-use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{Manager, Theme};
 use tauri_plugin_autostart::MacosLauncher;
-use tauri_plugin_updater::UpdaterExt;
-use std::time::Duration;
-use serde::Serialize;
+use tauri_plugin_log::{Target, TargetKind};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+mod about;
+mod accessibility;
+pub mod app_state;
+mod auto_reset;
+pub mod autostart;
+mod capabilities;
+mod changelog;
+mod clipboard;
+mod deep_link;
+pub mod dnd;
+mod documents;
+mod drafts;
+mod export;
+pub mod features;
+mod file_open;
+mod headless;
+mod history;
+mod html;
+mod instance;
+mod integrity;
+mod licenses;
+mod links;
+pub mod locale;
+mod menu;
+mod metrics;
+mod notification;
+mod paths;
+mod platform;
+mod plugins;
+mod print;
+mod quick_capture;
+mod recent;
+mod request_headers;
+mod retry;
+pub mod safe_mode;
+pub mod settings;
+mod share;
+mod shell;
+mod shortcuts;
+mod splash;
+mod system_prefs;
+pub mod startup;
+pub mod tasks;
+mod theme;
+mod thumbnail;
+mod title;
+mod touch;
+mod tray;
+mod updater;
+mod webview;
+pub mod window;
+mod workspace;
+
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
 
 #[tauri::command]
 async fn close_splashscreen(window: tauri::Window) {
-    if let Some(splashscreen) = window.get_webview_window("splashscreen") {
-        let _ = splashscreen.close();
-    }
-    if let Some(main_window) = window.get_webview_window("main") {
-        let _ = main_window.show();
-    }
+    splash::finish(window.app_handle());
+    startup::flush(window.app_handle());
 }
 
+/// Whether a login item is registered; what it passes comes from `get_autostart_args`.
 #[tauri::command]
 async fn get_autostart_enabled(app: tauri::AppHandle) -> Result<bool, String> {
     use tauri_plugin_autostart::ManagerExt;
@@ -27,14 +81,14 @@ async fn get_autostart_enabled(app: tauri::AppHandle) -> Result<bool, String> {
 #[tauri::command]
 async fn set_autostart_enabled(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
     use tauri_plugin_autostart::ManagerExt;
-    let autostart = app.autolaunch();
     if enabled {
-        autostart.enable().map_err(|e| e.to_string())
+        autostart::enable(&app)
     } else {
-        autostart.disable().map_err(|e| e.to_string())
+        app.autolaunch().disable().map_err(|e| e.to_string())
     }
 }
 
+/// The theme the window actually shows, also when the preference is `auto`.
 #[tauri::command]
 async fn get_system_theme(window: tauri::Window) -> Result<String, String> {
     match window.theme() {
@@ -44,49 +98,12 @@ async fn get_system_theme(window: tauri::Window) -> Result<String, String> {
     }
 }
 
+/// `theme` is `dark`, `light` or `auto` (follow the OS); remembered for the next launch.
 #[tauri::command]
 async fn set_window_theme(window: tauri::Window, theme: String) -> Result<(), String> {
-    let tauri_theme = match theme.as_str() {
-        "dark" => Some(Theme::Dark),
-        "light" => Some(Theme::Light),
-        _ => None,
-    };
-    window.set_theme(tauri_theme).map_err(|e| e.to_string())
-}
-
-#[derive(Clone, Serialize)]
-struct UpdateCheckResult {
-    available: bool,
-    version: Option<String>,
-    current_version: String,
-    body: Option<String>,
-}
-
-#[tauri::command]
-async fn check_for_update(app: tauri::AppHandle) -> Result<UpdateCheckResult, String> {
-    let current_version = app.package_info().version.to_string();
-
-    let updater = app.updater_builder().build().map_err(|e| e.to_string())?;
-
-    match updater.check().await {
-        Ok(Some(update)) => {
-            Ok(UpdateCheckResult {
-                available: true,
-                version: Some(update.version.clone()),
-                current_version,
-                body: update.body.clone(),
-            })
-        }
-        Ok(None) => {
-            Ok(UpdateCheckResult {
-                available: false,
-                version: None,
-                current_version,
-                body: None,
-            })
-        }
-        Err(e) => Err(e.to_string()),
-    }
+    let tauri_theme = theme::parse_preference(&theme)?;
+    window.set_theme(tauri_theme).map_err(|e| e.to_string())?;
+    theme::save_preference(window.app_handle(), &theme)
 }
 
 #[tauri::command]
@@ -107,216 +124,371 @@ fn toggle_window_visibility(app: &tauri::AppHandle) {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
-        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+    if let Some(request) = headless::requested() {
+        headless::run(request);
+    }
+    let context = tauri::generate_context!();
+    let startup_trace = startup::StartupTrace::default();
+    let safe_mode = safe_mode::requested();
+    let portable = paths::Portable::detect();
+    let log_target = match portable.dir("logs") {
+        Some(path) => TargetKind::Folder { path, file_name: None },
+        None => TargetKind::LogDir { file_name: None },
+    };
+    let mut window_state =
+        tauri_plugin_window_state::Builder::new().with_denylist(&[quick_capture::WINDOW_LABEL, print::WINDOW_LABEL]);
+    if let Some(config_dir) = portable.dir("config") {
+        let file = config_dir.join(tauri_plugin_window_state::DEFAULT_FILENAME);
+        window_state = window_state.with_filename(file.to_string_lossy());
+    }
+
+    let stale_instance = if safe_mode { None } else { instance::find_stale(&context.config().identifier) };
+
+    let mut builder = tauri::Builder::default();
+    if !safe_mode && stale_instance.is_none_or(|_| instance::clear_stale_lock(&context.config().identifier)) {
+        builder = builder.plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
             if let Some(window) = app.get_webview_window("main") {
                 let _ = window.show();
                 let _ = window.set_focus();
             }
-        }))
+            deep_link::handle_args(app, &args);
+            file_open::handle_args(app, &args);
+        }));
+    }
+    builder = builder
         .plugin(tauri_plugin_autostart::init(
             MacosLauncher::LaunchAgent,
-            Some(vec!["--minimized"]),
+            Some(vec![autostart::AUTOSTART_ARG, autostart::MINIMIZED_ARG]),
         ))
+        .plugin(
+            tauri_plugin_log::Builder::new()
+                .targets([Target::new(TargetKind::Stdout), Target::new(log_target)])
+                .level(if safe_mode {
+                    log::LevelFilter::Debug
+                } else {
+                    log::LevelFilter::Info
+                })
+                .build(),
+        )
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
-        .plugin(tauri_plugin_notification::init())
-        .plugin(tauri_plugin_window_state::Builder::new().build())
-        .plugin(tauri_plugin_updater::Builder::new().build())
-        .plugin(tauri_plugin_store::Builder::new().build())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build());
+    builder
+        .manage(plugins::Plugins::default())
+        .manage(safe_mode::SafeMode(safe_mode))
+        .manage(app_state::AppState::default())
+        .manage(deep_link::PendingAuthLinks::default())
+        .manage(webview::PageLoad::default())
+        .on_page_load(webview::on_page_load)
+        // Webviews created after startup, including the frontend's `external-*` windows.
+        .plugin(
+            tauri::plugin::Builder::<tauri::Wry>::new("window-setup")
+                .on_webview_ready(|webview| {
+                    request_headers::attach(&webview);
+                    window::apply_content_protection(&webview.window());
+                })
+                .build(),
+        )
+        .on_window_event(window::track_focus)
+        .manage(portable)
+        .manage(startup::StartupNotices::default())
+        .manage(startup_trace)
+        .manage(tasks::TaskRegistry::default())
+        .manage(export::StreamingExports::default())
+        .manage(window::ContentProtection::default())
+        .manage(window::WindowOrder::default())
+        .manage(window::AttentionRequests::default())
+        .manage(window::ZenWindows::default())
+        .manage(menu::AppMenu::default())
+        .manage(menu::DisabledMenuItems::default())
+        .manage(changelog::Changelog::default())
+        .manage(tray::TrayRecent::default())
+        .manage(tray::TrayQuickActions::default())
+        .manage(tray::TrayStatus::default())
+        .manage(clipboard::ClipboardHistory::default())
+        .manage(documents::UnlockTokens::default())
+        .manage(drafts::SavedDraftHashes::default())
+        .manage(shortcuts::GlobalShortcuts::default())
+        .manage(share::SigningKey::default())
+        .manage(retry::RetryQueue::default())
+        .manage(notification::ScheduledNotifications::default())
+        .manage(title::WindowTitles::default())
         .invoke_handler(tauri::generate_handler![
             close_splashscreen,
+            deep_link::frontend_ready,
+            deep_link::set_oauth_state,
+            deep_link::get_deep_link_scheme,
+            headless::get_headless_request,
             get_autostart_enabled,
             set_autostart_enabled,
+            autostart::get_autostart_details,
+            autostart::repair_autostart,
+            autostart::verify_autostart,
+            autostart::get_started_minimized,
+            autostart::was_autostarted,
+            autostart::set_start_minimized,
+            autostart::get_start_minimized,
+            autostart::set_autostart_args,
+            autostart::get_autostart_args,
             get_system_theme,
             set_window_theme,
-            check_for_update,
-            get_app_version
+            theme::get_accent_color,
+            system_prefs::get_reduced_motion,
+            system_prefs::get_high_contrast,
+            updater::check_for_update,
+            updater::get_last_update_check,
+            updater::download_and_install,
+            updater::download_and_install_update,
+            updater::set_update_channel,
+            updater::get_update_channel,
+            updater::skip_update_version,
+            updater::clear_skipped_version,
+            updater::set_update_check_interval,
+            updater::get_update_check_interval,
+            updater::relaunch_app,
+            changelog::get_changelog,
+            tray::set_tray_visible,
+            tray::get_tray_visible,
+            tray::set_tray_tooltip,
+            tray::set_tray_badge,
+            tray::update_tray_recent,
+            tray::set_tray_quick_actions,
+            tray::set_tray_status,
+            tray::set_tray_left_click_action,
+            tray::get_tray_left_click_action,
+            get_app_version,
+            thumbnail::render_thumbnail,
+            tasks::list_active_tasks,
+            tasks::cancel_task,
+            window::set_content_protected,
+            window::get_content_protected,
+            window::set_ui_density,
+            window::get_ui_density,
+            links::open_web_version,
+            workspace::save_layout,
+            workspace::load_layout,
+            export::export_pdf,
+            export::export_docx,
+            print::print_content,
+            notification::show_notification,
+            notification::schedule_notification,
+            notification::cancel_notification,
+            export::stream::export_start,
+            export::stream::export_append,
+            export::stream::export_finish,
+            export::batch::export_batch,
+            export::webdav::export_to_webdav,
+            export::webdav::set_webdav_config,
+            export::webdav::get_webdav_config,
+            export::get_last_export_dir,
+            export::set_last_export_dir,
+            shell::run_allowed_command,
+            shortcuts::get_shortcut_map,
+            shortcuts::set_shortcut_override,
+            menu::set_menu_language,
+            menu::set_menu_item_enabled,
+            dnd::set_do_not_disturb,
+            dnd::get_do_not_disturb,
+            capabilities::get_capabilities,
+            documents::pin_document,
+            documents::unpin_document,
+            documents::list_pinned_documents,
+            recent::add_recent_document,
+            recent::push_recent_file,
+            recent::remove_recent_document,
+            recent::get_recent_documents,
+            safe_mode::start_safe_mode,
+            safe_mode::reset_settings,
+            about::show_about_dialog,
+            licenses::get_licenses,
+            features::get_feature_flags,
+            features::set_feature_flag,
+            features::reset_feature_flags,
+            auto_reset::set_auto_reset,
+            auto_reset::get_auto_reset,
+            auto_reset::report_activity,
+            auto_reset::set_generation_in_flight,
+            paths::is_portable,
+            clipboard::set_clipboard_history_enabled,
+            clipboard::get_clipboard_history_enabled,
+            clipboard::record_clipboard_copy,
+            clipboard::get_clipboard_history,
+            clipboard::clipboard_history_restore,
+            clipboard::clear_clipboard_history,
+            clipboard::generate_from_clipboard,
+            share::create_export_token,
+            share::validate_export_token,
+            touch::set_touch_mode,
+            touch::get_touch_mode,
+            metrics::get_process_metrics,
+            window::cycle_window,
+            startup::get_startup_trace,
+            window::minimize_window,
+            window::maximize_window,
+            window::unmaximize_window,
+            window::toggle_maximize_window,
+            window::start_window_drag,
+            window::set_always_on_top,
+            window::get_always_on_top,
+            window::get_window_bounds,
+            window::set_window_bounds,
+            window::center_window,
+            window::set_window_title,
+            window::set_window_opacity,
+            window::flash_window,
+            window::set_zen_mode,
+            title::set_title_format,
+            title::get_title_format,
+            title::set_document_title,
+            accessibility::dump_accessibility_labels,
+            accessibility::dump_menu_structure,
+            retry::enqueue_retry,
+            retry::report_retry_result,
+            retry::list_retries,
+            retry::cancel_retry,
+            webview::soft_reload,
+            splash::set_disable_splash,
+            splash::get_disable_splash,
+            links::open_scheme_url,
+            links::set_extra_url_schemes,
+            links::get_url_scheme_allowlist,
+            documents::request_unlock_token,
+            documents::set_document_locked,
+            documents::is_document_locked,
+            documents::content_hash,
+            drafts::save_draft,
+            drafts::load_draft,
+            drafts::delete_draft,
+            history::save_history_snapshot,
+            history::list_history,
+            history::restore_history,
+            webview::get_webview_load_error,
+            webview::reload_webview,
+            request_headers::set_request_header,
+            request_headers::get_request_headers,
+            app_state::get_app_state,
+            integrity::verify_install_integrity,
+            integrity::set_verify_integrity_on_startup,
+            integrity::get_verify_integrity_on_startup,
+            locale::format_datetime,
+            locale::format_number,
+            locale::set_app_locale,
+            locale::get_app_locale,
+            quick_capture::open_quick_capture,
+            quick_capture::close_quick_capture,
+            quick_capture::submit_quick_capture
         ])
-        .setup(|app| {
+        .setup(move |app| {
+            // Optional plugins: a failure disables the feature instead of aborting the launch.
+            plugins::register(app.handle(), plugins::STORE, tauri_plugin_store::Builder::new().build());
+            plugins::register(app.handle(), plugins::UPDATER, tauri_plugin_updater::Builder::new().build());
+            plugins::register(app.handle(), plugins::NOTIFICATION, tauri_plugin_notification::init());
+            if !safe_mode {
+                plugins::register(app.handle(), plugins::WINDOW_STATE, window_state.build());
+            }
+            startup::mark(app.handle(), startup::PLUGINS_INITIALIZED);
+            paths::log_mode(app.handle());
+            settings::recover_if_corrupt(app.handle());
+            if !safe_mode {
+                theme::apply_preference(app.handle());
+            }
+            splash::init(app.handle());
+            dnd::init(app.handle());
+            auto_reset::init(app.handle());
+            touch::init(app.handle());
+            retry::init(app.handle());
+            request_headers::init(app.handle());
+            for window in app.webview_windows().values() {
+                request_headers::attach(window.as_ref());
+                window::apply_content_protection(&window.as_ref().window());
+            }
+            if safe_mode {
+                safe_mode::announce(app.handle());
+            } else {
+                instance::start_heartbeat(app.handle(), stale_instance);
+            }
+
             #[cfg(desktop)]
             {
                 use tauri_plugin_deep_link::DeepLinkExt;
 
-                let file_menu = Submenu::with_items(
-                    app,
-                    "Datei",
-                    true,
-                    &[
-                        &MenuItem::with_id(app, "new", "Neuer Text", true, Some("CmdOrCtrl+N"))?,
-                        &PredefinedMenuItem::separator(app)?,
-                        &MenuItem::with_id(app, "settings", "Einstellungen...", true, Some("CmdOrCtrl+,"))?,
-                        &PredefinedMenuItem::separator(app)?,
-                        &PredefinedMenuItem::quit(app, Some("Beenden"))?,
-                    ],
-                )?;
-
-                let edit_menu = Submenu::with_items(
-                    app,
-                    "Bearbeiten",
-                    true,
-                    &[
-                        &PredefinedMenuItem::undo(app, Some("Rückgängig"))?,
-                        &PredefinedMenuItem::redo(app, Some("Wiederholen"))?,
-                        &PredefinedMenuItem::separator(app)?,
-                        &PredefinedMenuItem::cut(app, Some("Ausschneiden"))?,
-                        &PredefinedMenuItem::copy(app, Some("Kopieren"))?,
-                        &PredefinedMenuItem::paste(app, Some("Einfügen"))?,
-                        &PredefinedMenuItem::select_all(app, Some("Alles auswählen"))?,
-                    ],
-                )?;
-
-                let view_menu = Submenu::with_items(
-                    app,
-                    "Ansicht",
-                    true,
-                    &[
-                        &MenuItem::with_id(app, "reload", "Neu laden", true, Some("CmdOrCtrl+R"))?,
-                        &PredefinedMenuItem::separator(app)?,
-                        &MenuItem::with_id(app, "fullscreen", "Vollbild", true, Some("F11"))?,
-                        &MenuItem::with_id(app, "zoom_in", "Vergrößern", true, Some("CmdOrCtrl+Plus"))?,
-                        &MenuItem::with_id(app, "zoom_out", "Verkleinern", true, Some("CmdOrCtrl+Minus"))?,
-                        &MenuItem::with_id(app, "zoom_reset", "Originalgröße", true, Some("CmdOrCtrl+0"))?,
-                    ],
-                )?;
-
-                let help_menu = Submenu::with_items(
-                    app,
-                    "Hilfe",
-                    true,
-                    &[
-                        &MenuItem::with_id(app, "check_updates", "Nach Updates suchen...", true, None::<&str>)?,
-                        &PredefinedMenuItem::separator(app)?,
-                        &MenuItem::with_id(app, "docs", "Dokumentation", true, None::<&str>)?,
-                        &MenuItem::with_id(app, "feedback", "Feedback senden", true, None::<&str>)?,
-                        &PredefinedMenuItem::separator(app)?,
-                        &MenuItem::with_id(app, "about", "Über Grünerator", true, None::<&str>)?,
-                    ],
-                )?;
-
-                let app_menu = Menu::with_items(app, &[&file_menu, &edit_menu, &view_menu, &help_menu])?;
-
+                menu::rebuild(app.handle())?;
                 if let Some(main_window) = app.get_webview_window("main") {
-                    let _ = main_window.set_menu(app_menu)?;
-                    main_window.on_menu_event(|window, event| {
-                        match event.id.as_ref() {
-                            "new" => {
-                                let _ = window.emit("menu-new", ());
-                            }
-                            "settings" => {
-                                let _ = window.emit("menu-settings", ());
-                            }
-                            "reload" => {
-                                let _ = window.emit("menu-reload", ());
-                            }
-                            "fullscreen" => {
-                                if let Ok(is_fullscreen) = window.is_fullscreen() {
-                                    let _ = window.set_fullscreen(!is_fullscreen);
-                                }
-                            }
-                            "zoom_in" => {
-                                let _ = window.emit("menu-zoom", "in");
-                            }
-                            "zoom_out" => {
-                                let _ = window.emit("menu-zoom", "out");
-                            }
-                            "zoom_reset" => {
-                                let _ = window.emit("menu-zoom", "reset");
-                            }
-                            "docs" => {
-                                let _ = window.emit("menu-open-url", "https://gruenerator.de/");
-                            }
-                            "feedback" => {
-                                let _ = window.emit("menu-open-url", "https://gitlab.com/Netzbegruenung/gruenerator/-/issues");
-                            }
-                            "about" => {
-                                let _ = window.emit("menu-about", ());
-                            }
-                            "check_updates" => {
-                                let _ = window.emit("menu-check-updates", ());
-                            }
-                            _ => {}
-                        }
-                    });
+                    main_window.on_menu_event(menu::handle_menu_event);
                 }
+                startup::mark(app.handle(), startup::MENU_BUILT);
 
-                let show_hide = MenuItem::with_id(app, "tray_show_hide", "Anzeigen/Verbergen", true, None::<&str>)?;
-                let separator = MenuItem::with_id(app, "tray_separator", "─────────────", false, None::<&str>)?;
-                let quit = MenuItem::with_id(app, "tray_quit", "Beenden", true, None::<&str>)?;
-
-                let tray_menu = Menu::with_items(app, &[&show_hide, &separator, &quit])?;
+                if !safe_mode && tray::is_enabled(app.handle()) {
+                    let _tray = tray::create(app.handle())?;
+                    startup::mark(app.handle(), startup::TRAY_BUILT);
+                }
+                autostart::init_launch(app.handle(), app.tray_by_id(tray::TRAY_ID).is_some());
 
-                let _tray = TrayIconBuilder::new()
-                    .icon(app.default_window_icon().unwrap().clone())
-                    .tooltip("Grünerator")
-                    .menu(&tray_menu)
-                    .show_menu_on_left_click(false)
-                    .on_menu_event(|app, event| {
-                        match event.id.as_ref() {
-                            "tray_show_hide" => toggle_window_visibility(app),
-                            "tray_quit" => {
-                                app.exit(0);
-                            }
-                            _ => {}
-                        }
-                    })
-                    .on_tray_icon_event(|tray, event| {
-                        if let TrayIconEvent::Click {
-                            button: MouseButton::Left,
-                            button_state: MouseButtonState::Up,
-                            ..
-                        } = event
-                        {
-                            let app = tray.app_handle();
-                            toggle_window_visibility(app);
-                        }
-                    })
-                    .build(app)?;
+                let scheme = deep_link::active_scheme(app.handle());
+                log::info!("Deep-link scheme: {}://", scheme);
 
                 let handle = app.handle().clone();
                 app.deep_link().on_open_url(move |event| {
                     for url in event.urls() {
-                        let url_str = url.to_string();
-                        if url_str.starts_with("gruenerator://auth/callback") {
-                            let _ = handle.emit("deep-link-auth", url_str);
-                        }
+                        deep_link::handle_url(&handle, &scheme, &url);
                     }
                 });
+                startup::mark(app.handle(), startup::DEEP_LINK_REGISTERED);
+                // Windows and Linux pass the link or file that launched the app as an argument.
+                let args: Vec<String> = std::env::args().collect();
+                deep_link::handle_args(app.handle(), &args);
+                file_open::handle_args(app.handle(), &args);
 
                 if let Some(main_window) = app.get_webview_window("main") {
                     main_window.open_devtools();
                 }
 
+                if !safe_mode {
+                    window::apply_ui_density(app.handle());
+                    window::apply_always_on_top(app.handle());
+                    window::restore_opacity(app.handle());
+                }
+                platform::check_os_version(app.handle());
+                platform::check_architecture(app.handle());
+                if let Err(e) = autostart::verify(app.handle()) {
+                    log::warn!("Failed to verify autostart: {}", e);
+                }
+                integrity::verify_on_startup(app.handle());
+                updater::check_install_writable(app.handle());
+                updater::init(app.handle());
+                shortcuts::register_all_global(app.handle());
+
                 if let Some(main_window) = app.get_webview_window("main") {
-                    let window_clone = main_window.clone();
-                    main_window.on_window_event(move |event| {
-                        if let tauri::WindowEvent::ThemeChanged(theme) = event {
-                            let theme_str = match theme {
-                                Theme::Dark => "dark",
-                                Theme::Light => "light",
-                                _ => "light",
-                            };
-                            let _ = window_clone.emit("system-theme-changed", theme_str);
-                        }
-                    });
+                    theme::watch(&main_window);
+                    theme::watch_accent(app.handle());
+                    system_prefs::init(app.handle());
+                    window::watch_scale_factor(&main_window);
                 }
 
                 let app_handle = app.handle().clone();
                 std::thread::spawn(move || {
                     std::thread::sleep(Duration::from_secs(3));
-                    if let Some(splashscreen) = app_handle.get_webview_window("splashscreen") {
-                        let _ = splashscreen.close();
-                    }
-                    if let Some(main_window) = app_handle.get_webview_window("main") {
-                        let _ = main_window.show();
+                    // If the main window failed to load, the splash is showing the error; keep it.
+                    if !webview::load_failed(&app_handle) {
+                        splash::finish(&app_handle);
                     }
+                    startup::flush(&app_handle);
                 });
             }
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(context)
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            #[cfg(target_os = "macos")]
+            if let tauri::RunEvent::Opened { urls } = &event {
+                file_open::handle_urls(app, urls);
+            }
+            #[cfg(not(target_os = "macos"))]
+            let _ = (app, event);
+        });
 }
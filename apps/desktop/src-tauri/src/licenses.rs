@@ -0,0 +1,62 @@
+//! Third-party license texts for the acknowledgements screen, embedded at build time from
+//! `cargo bundle-licenses` output (see `licenses/README.md`).
+
+use serde::{Deserialize, Serialize};
+
+const BUNDLED: &str = include_str!(concat!(env!("OUT_DIR"), "/third_party_licenses.json"));
+
+#[derive(Deserialize)]
+struct BundledLicenses {
+    third_party_libraries: Vec<ThirdPartyLibrary>,
+}
+
+#[derive(Deserialize)]
+struct ThirdPartyLibrary {
+    package_name: String,
+    package_version: String,
+    license: String,
+    #[serde(default)]
+    licenses: Vec<LicenseText>,
+}
+
+#[derive(Deserialize)]
+struct LicenseText {
+    text: String,
+}
+
+#[derive(Clone, Serialize)]
+pub struct LicenseEntry {
+    name: String,
+    version: String,
+    license: String,
+    text: String,
+}
+
+#[tauri::command]
+pub async fn get_licenses() -> Result<Vec<LicenseEntry>, String> {
+    if BUNDLED.trim().is_empty() {
+        log::warn!("No third-party licenses bundled with this build");
+        return Ok(Vec::new());
+    }
+
+    let bundled: BundledLicenses = serde_json::from_str(BUNDLED).map_err(|e| {
+        log::warn!("Bundled third-party licenses are unreadable: {}", e);
+        e.to_string()
+    })?;
+
+    Ok(bundled
+        .third_party_libraries
+        .into_iter()
+        .map(|library| LicenseEntry {
+            name: library.package_name,
+            version: library.package_version,
+            license: library.license,
+            text: library
+                .licenses
+                .into_iter()
+                .map(|license| license.text)
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+        })
+        .collect())
+}
@@ -0,0 +1,114 @@
+use serde::Serialize;
+use tauri::Emitter;
+use tauri_plugin_opener::OpenerExt;
+use url::Url;
+
+use crate::settings;
+
+const WEB_APP_ORIGIN: &str = "https://gruenerator.de";
+const EXTRA_SCHEMES_KEY: &str = "extra_url_schemes";
+/// Note-taking and mail apps a generated text is commonly sent to.
+const DEFAULT_SCHEMES: &[&str] = &["mailto", "obsidian", "logseq", "joplin", "onenote", "bear", "things"];
+/// Never allowed, also not through the setting: these run code or reach local files.
+const BLOCKED_SCHEMES: &[&str] = &["file", "javascript", "vbscript", "data", "blob", "about", "ms-msdt", "search-ms"];
+
+#[derive(Clone, Serialize)]
+struct WebFallback {
+    path: String,
+    url: String,
+}
+
+/// Resolves `path` against the hosted web app and refuses anything that would leave its
+/// origin (absolute URLs, protocol-relative `//host` paths, backslash tricks).
+fn web_version_url(path: &str) -> Result<Url, String> {
+    if !path.starts_with('/') || path.starts_with("//") || path.contains('\\') {
+        return Err(format!("invalid web path: {}", path));
+    }
+    let base = Url::parse(WEB_APP_ORIGIN).map_err(|e| e.to_string())?;
+    let url = base.join(path).map_err(|e| e.to_string())?;
+    if url.origin() != base.origin() {
+        return Err(format!("invalid web path: {}", path));
+    }
+    Ok(url)
+}
+
+/// Opens the hosted web app for features the desktop build doesn't offer. The desktop
+/// session is intentionally not forwarded; the web app signs in through the same SSO.
+#[tauri::command]
+pub async fn open_web_version(app: tauri::AppHandle, path: String) -> Result<(), String> {
+    let url = web_version_url(&path)?;
+    app.opener()
+        .open_url(url.as_str(), None::<&str>)
+        .map_err(|e| e.to_string())?;
+    let _ = app.emit(
+        "opened-web-fallback",
+        WebFallback {
+            path,
+            url: url.to_string(),
+        },
+    );
+    Ok(())
+}
+
+#[derive(Clone, Serialize)]
+pub struct SchemeAllowlist {
+    defaults: Vec<&'static str>,
+    extra: Vec<String>,
+}
+
+fn extra_schemes(app: &tauri::AppHandle) -> Vec<String> {
+    settings::get(app, EXTRA_SCHEMES_KEY).unwrap_or_default()
+}
+
+fn validate_scheme(scheme: &str) -> Result<String, String> {
+    let scheme = scheme.trim().trim_end_matches(':').to_ascii_lowercase();
+    let valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    if !valid || BLOCKED_SCHEMES.contains(&scheme.as_str()) {
+        return Err(format!("invalid_scheme: {}", scheme));
+    }
+    Ok(scheme)
+}
+
+/// Opens `url` in the app registered for its scheme, e.g. `obsidian://new?content=…`.
+/// Fails with `scheme_not_allowed: <scheme>` unless the scheme is a default or was added with
+/// `set_extra_url_schemes`.
+#[tauri::command]
+pub async fn open_scheme_url(app: tauri::AppHandle, url: String) -> Result<(), String> {
+    let parsed = Url::parse(&url).map_err(|e| format!("invalid_url: {}", e))?;
+    let scheme = parsed.scheme();
+    let allowed = !BLOCKED_SCHEMES.contains(&scheme)
+        && (DEFAULT_SCHEMES.contains(&scheme) || extra_schemes(&app).iter().any(|extra| extra == scheme));
+    if !allowed {
+        return Err(format!("scheme_not_allowed: {}", scheme));
+    }
+    app.opener()
+        .open_url(parsed.as_str(), None::<&str>)
+        .map_err(|e| e.to_string())
+}
+
+/// Replaces the user-added schemes, given as `name` or `name:`. Blocked or malformed ones
+/// fail the whole call with `invalid_scheme: <scheme>`.
+#[tauri::command]
+pub async fn set_extra_url_schemes(app: tauri::AppHandle, schemes: Vec<String>) -> Result<SchemeAllowlist, String> {
+    let mut extra = schemes
+        .iter()
+        .map(|scheme| validate_scheme(scheme))
+        .collect::<Result<Vec<_>, _>>()?;
+    extra.sort();
+    extra.dedup();
+    extra.retain(|scheme| !DEFAULT_SCHEMES.contains(&scheme.as_str()));
+    settings::set(&app, EXTRA_SCHEMES_KEY, &extra)?;
+    Ok(SchemeAllowlist {
+        defaults: DEFAULT_SCHEMES.to_vec(),
+        extra,
+    })
+}
+
+#[tauri::command]
+pub async fn get_url_scheme_allowlist(app: tauri::AppHandle) -> Result<SchemeAllowlist, String> {
+    Ok(SchemeAllowlist {
+        defaults: DEFAULT_SCHEMES.to_vec(),
+        extra: extra_schemes(&app),
+    })
+}
@@ -0,0 +1,179 @@
+//! Date and number formatting in the app locale, so recent documents, exports and the UI show
+//! the same `14.10.2026 09:30:00` / `1.234,5` regardless of what the webview's `Intl` supports.
+//! Conventions come from the glibc locale data in `pure-rust-locales`; unknown locales fall
+//! back to `de-DE`.
+
+use chrono::{Local, Locale, TimeZone};
+use pure_rust_locales::locale_match;
+
+use crate::settings;
+
+const LOCALE_KEY: &str = "app_locale";
+const DEFAULT_LOCALE: Locale = Locale::de_DE;
+
+/// Accepts BCP 47 (`de-DE`) as well as POSIX (`de_DE`) tags.
+fn parse(tag: &str) -> Option<Locale> {
+    tag.trim().replace('-', "_").parse().ok()
+}
+
+fn tag(locale: Locale) -> String {
+    format!("{:?}", locale).replace('_', "-")
+}
+
+/// `tag` as a locale, `de-DE` if it is unknown.
+fn resolve(tag: &str) -> Locale {
+    parse(tag).unwrap_or_else(|| {
+        log::warn!("Unknown locale {}, falling back to {}", tag, self::tag(DEFAULT_LOCALE));
+        DEFAULT_LOCALE
+    })
+}
+
+pub fn current(app: &tauri::AppHandle) -> Locale {
+    settings::get::<String>(app, LOCALE_KEY)
+        .and_then(|tag| parse(&tag))
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+/// `style` is `date` (`14.10.2026`), `time` (`09:30:00`) or `datetime` (both), in local time.
+pub fn format_datetime_in(locale: Locale, epoch_ms: i64, style: &str) -> Result<String, String> {
+    format_in_zone(locale, &Local, epoch_ms, style)
+}
+
+fn format_in_zone<Tz: TimeZone>(locale: Locale, zone: &Tz, epoch_ms: i64, style: &str) -> Result<String, String>
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let format = match style {
+        "date" => "%x",
+        "time" => "%X",
+        "datetime" => "%x %X",
+        _ => return Err(format!("unknown_style: {}", style)),
+    };
+    // A UTC instant always maps to exactly one local time, also across DST changes.
+    let datetime = zone
+        .timestamp_millis_opt(epoch_ms)
+        .single()
+        .ok_or_else(|| "invalid_timestamp".to_string())?;
+    Ok(datetime.format_localized(format, locale).to_string())
+}
+
+/// `style` is `integer` (rounded) or `decimal` (up to two fraction digits).
+pub fn format_number_in(locale: Locale, value: f64, style: &str) -> Result<String, String> {
+    if !value.is_finite() {
+        return Err("invalid_number".to_string());
+    }
+    let fraction_digits = match style {
+        "integer" => 0,
+        "decimal" => 2,
+        _ => return Err(format!("unknown_style: {}", style)),
+    };
+
+    let decimal_point = locale_match!(locale => LC_NUMERIC::DECIMAL_POINT);
+    let thousands_sep = locale_match!(locale => LC_NUMERIC::THOUSANDS_SEP);
+    let grouping = locale_match!(locale => LC_NUMERIC::GROUPING)
+        .first()
+        .copied()
+        .filter(|size| *size > 0)
+        .unwrap_or(3) as usize;
+
+    let formatted = format!("{:.*}", fraction_digits, value.abs());
+    let (integer, fraction) = formatted.split_once('.').unwrap_or((&formatted, ""));
+    let fraction = fraction.trim_end_matches('0');
+
+    let mut result = String::new();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % grouping == 0 {
+            result.push_str(thousands_sep);
+        }
+        result.push(digit);
+    }
+    if !fraction.is_empty() {
+        result.push_str(decimal_point);
+        result.push_str(fraction);
+    }
+    if value < 0.0 && result.chars().any(|c| c.is_ascii_digit() && c != '0') {
+        result.insert(0, '-');
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+pub async fn format_datetime(app: tauri::AppHandle, epoch_ms: i64, style: String) -> Result<String, String> {
+    format_datetime_in(current(&app), epoch_ms, &style)
+}
+
+#[tauri::command]
+pub async fn format_number(app: tauri::AppHandle, value: f64, style: String) -> Result<String, String> {
+    format_number_in(current(&app), value, &style)
+}
+
+/// Stores the app locale and returns the tag actually used, `de-DE` if `locale` is unknown.
+#[tauri::command]
+pub async fn set_app_locale(app: tauri::AppHandle, locale: String) -> Result<String, String> {
+    let resolved = resolve(&locale);
+    settings::set(&app, LOCALE_KEY, tag(resolved))?;
+    Ok(tag(resolved))
+}
+
+#[tauri::command]
+pub async fn get_app_locale(app: tauri::AppHandle) -> Result<String, String> {
+    Ok(tag(current(&app)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::Europe::Berlin;
+
+    /// Milliseconds since the epoch of a UTC time on `day` of `month` 2026.
+    fn utc_ms(month: u32, day: u32, hour: u32, minute: u32, second: u32) -> i64 {
+        chrono::Utc
+            .with_ymd_and_hms(2026, month, day, hour, minute, second)
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    fn berlin(epoch_ms: i64) -> String {
+        format_in_zone(Locale::de_DE, &Berlin, epoch_ms, "datetime").unwrap()
+    }
+
+    #[test]
+    fn spring_forward_skips_the_missing_hour() {
+        // 29.03.2026: clocks jump from 02:00 CET to 03:00 CEST at 01:00 UTC.
+        assert_eq!(berlin(utc_ms(3, 29, 0, 59, 59)), "29.03.2026 01:59:59");
+        assert_eq!(berlin(utc_ms(3, 29, 1, 0, 0)), "29.03.2026 03:00:00");
+    }
+
+    #[test]
+    fn fall_back_repeats_the_hour() {
+        // 25.10.2026: clocks go back from 03:00 CEST to 02:00 CET at 01:00 UTC, so 02:30
+        // happens twice, an hour apart.
+        assert_eq!(berlin(utc_ms(10, 25, 0, 30, 0)), "25.10.2026 02:30:00");
+        assert_eq!(berlin(utc_ms(10, 25, 0, 59, 59)), "25.10.2026 02:59:59");
+        assert_eq!(berlin(utc_ms(10, 25, 1, 0, 0)), "25.10.2026 02:00:00");
+        assert_eq!(berlin(utc_ms(10, 25, 1, 30, 0)), "25.10.2026 02:30:00");
+        assert_eq!(berlin(utc_ms(10, 25, 2, 0, 0)), "25.10.2026 03:00:00");
+    }
+
+    #[test]
+    fn date_and_time_styles_around_a_transition() {
+        let ms = utc_ms(3, 29, 1, 30, 0);
+        assert_eq!(format_in_zone(Locale::de_DE, &Berlin, ms, "date").unwrap(), "29.03.2026");
+        assert_eq!(format_in_zone(Locale::de_DE, &Berlin, ms, "time").unwrap(), "03:30:00");
+        assert!(format_in_zone(Locale::de_DE, &Berlin, ms, "weekday").is_err());
+    }
+
+    #[test]
+    fn unknown_locales_fall_back_to_de_de() {
+        assert_eq!(resolve("xx-XX"), Locale::de_DE);
+        assert_eq!(resolve(""), Locale::de_DE);
+        assert_eq!(resolve("en-US"), Locale::en_US);
+        assert_eq!(resolve("fr_FR"), Locale::fr_FR);
+
+        let ms = utc_ms(10, 25, 1, 30, 0);
+        assert_eq!(
+            format_in_zone(resolve("xx-XX"), &Berlin, ms, "datetime").unwrap(),
+            "25.10.2026 02:30:00"
+        );
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use tauri::menu::{Menu, MenuEvent, MenuItem, MenuItemKind, PredefinedMenuItem, Submenu};
+use tauri::{Emitter, Manager, Wry};
+
+use crate::{documents, recent, safe_mode, settings, shortcuts, touch, webview, window};
+
+const LANGUAGE_KEY: &str = "language";
+const LANGUAGES: &[&str] = &["de", "en"];
+
+/// Ids the frontend disabled with `set_menu_item_enabled`, kept across menu rebuilds.
+#[derive(Default)]
+pub struct DisabledMenuItems(Mutex<HashSet<String>>);
+
+fn item(app: &tauri::AppHandle, id: &str, label: &str) -> tauri::Result<MenuItem<Wry>> {
+    let accelerator = if touch::is_enabled(app) {
+        None
+    } else {
+        shortcuts::accelerator(app, id)
+    };
+    let enabled = !app.state::<DisabledMenuItems>().0.lock().unwrap().contains(id);
+    MenuItem::with_id(app, id, label, enabled, accelerator)
+}
+
+/// Menu labels by key: German, English.
+const LABELS: &[(&str, &str, &str)] = &[
+    ("file", "Datei", "File"),
+    ("new", "Neuer Text", "New Text"),
+    ("recent", "Zuletzt geöffnet", "Open Recent"),
+    ("clear_recent", "Liste leeren", "Clear Menu"),
+    ("export", "Exportieren", "Export"),
+    ("export_pdf", "Als PDF…", "As PDF…"),
+    ("export_docx", "Als Word…", "As Word…"),
+    ("export_markdown", "Als Markdown…", "As Markdown…"),
+    ("print", "Drucken…", "Print…"),
+    ("settings", "Einstellungen...", "Settings..."),
+    ("quit", "Beenden", "Quit"),
+    ("edit", "Bearbeiten", "Edit"),
+    ("undo", "Rückgängig", "Undo"),
+    ("redo", "Wiederholen", "Redo"),
+    ("cut", "Ausschneiden", "Cut"),
+    ("copy", "Kopieren", "Copy"),
+    ("paste", "Einfügen", "Paste"),
+    ("select_all", "Alles auswählen", "Select All"),
+    ("view", "Ansicht", "View"),
+    ("reload", "Neu laden", "Reload"),
+    ("fullscreen", "Vollbild", "Full Screen"),
+    ("zoom_in", "Vergrößern", "Zoom In"),
+    ("zoom_out", "Verkleinern", "Zoom Out"),
+    ("zoom_reset", "Originalgröße", "Actual Size"),
+    ("next_window", "Nächstes Fenster", "Next Window"),
+    ("previous_window", "Vorheriges Fenster", "Previous Window"),
+    ("help", "Hilfe", "Help"),
+    ("check_updates", "Nach Updates suchen...", "Check for Updates..."),
+    ("docs", "Dokumentation", "Documentation"),
+    ("feedback", "Feedback senden", "Send Feedback"),
+    ("about", "Über Grünerator", "About Grünerator"),
+    ("reset_settings", "Einstellungen zurücksetzen", "Reset Settings"),
+];
+
+fn label(lang: &str, key: &str) -> &'static str {
+    let (_, de, en) = LABELS
+        .iter()
+        .find(|(id, _, _)| *id == key)
+        .expect("menu label missing from LABELS");
+    if lang == "en" {
+        en
+    } else {
+        de
+    }
+}
+
+/// The menu language from the settings.
+fn language(app: &tauri::AppHandle) -> String {
+    settings::get::<String>(app, LANGUAGE_KEY)
+        .filter(|lang| LANGUAGES.contains(&lang.as_str()))
+        .unwrap_or_else(|| "de".to_string())
+}
+
+/// Builds the menu bar with labels in `lang` (`de` or `en`; anything else is German). Ids and
+/// accelerators are the same in every language.
+pub fn build_app_menu(app: &tauri::AppHandle, lang: &str) -> tauri::Result<Menu<Wry>> {
+    let file_menu = Submenu::with_items(
+        app,
+        label(lang, "file"),
+        true,
+        &[&item(app, "new", label(lang, "new"))?, &PredefinedMenuItem::separator(app)?],
+    )?;
+    let safe_mode = safe_mode::is_active(app);
+    let pinned = if safe_mode { Vec::new() } else { documents::pinned(app) };
+    for doc in &pinned {
+        file_menu.append(&MenuItem::with_id(
+            app,
+            format!("{}{}", documents::PINNED_ID_PREFIX, doc.id),
+            documents::menu_label(&doc.title),
+            true,
+            None::<&str>,
+        )?)?;
+    }
+    if !pinned.is_empty() {
+        file_menu.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+    let recent = if safe_mode { Vec::new() } else { recent::recent(app) };
+    if !recent.is_empty() {
+        let recent_menu = Submenu::new(app, label(lang, "recent"), true)?;
+        for doc in &recent {
+            recent_menu.append(&MenuItem::with_id(
+                app,
+                format!("{}{}", recent::RECENT_ID_PREFIX, doc.id),
+                &doc.label,
+                true,
+                None::<&str>,
+            )?)?;
+        }
+        recent_menu.append_items(&[
+            &PredefinedMenuItem::separator(app)?,
+            &item(app, recent::CLEAR_ID, label(lang, "clear_recent"))?,
+        ])?;
+        file_menu.append_items(&[&recent_menu, &PredefinedMenuItem::separator(app)?])?;
+    }
+    let export_menu = Submenu::with_items(
+        app,
+        label(lang, "export"),
+        true,
+        &[
+            &item(app, "export_pdf", label(lang, "export_pdf"))?,
+            &item(app, "export_docx", label(lang, "export_docx"))?,
+            &item(app, "export_markdown", label(lang, "export_markdown"))?,
+        ],
+    )?;
+    file_menu.append_items(&[
+        &export_menu,
+        &item(app, "print", label(lang, "print"))?,
+        &PredefinedMenuItem::separator(app)?,
+        &item(app, "settings", label(lang, "settings"))?,
+        &PredefinedMenuItem::separator(app)?,
+        &PredefinedMenuItem::quit(app, Some(label(lang, "quit")))?,
+    ])?;
+
+    let edit_menu = Submenu::with_items(
+        app,
+        label(lang, "edit"),
+        true,
+        &[
+            &PredefinedMenuItem::undo(app, Some(label(lang, "undo")))?,
+            &PredefinedMenuItem::redo(app, Some(label(lang, "redo")))?,
+            &PredefinedMenuItem::separator(app)?,
+            &PredefinedMenuItem::cut(app, Some(label(lang, "cut")))?,
+            &PredefinedMenuItem::copy(app, Some(label(lang, "copy")))?,
+            &PredefinedMenuItem::paste(app, Some(label(lang, "paste")))?,
+            &PredefinedMenuItem::select_all(app, Some(label(lang, "select_all")))?,
+        ],
+    )?;
+
+    let view_menu = Submenu::with_items(
+        app,
+        label(lang, "view"),
+        true,
+        &[
+            &item(app, "reload", label(lang, "reload"))?,
+            &PredefinedMenuItem::separator(app)?,
+            &item(app, "fullscreen", label(lang, "fullscreen"))?,
+            &item(app, "zoom_in", label(lang, "zoom_in"))?,
+            &item(app, "zoom_out", label(lang, "zoom_out"))?,
+            &item(app, "zoom_reset", label(lang, "zoom_reset"))?,
+            &PredefinedMenuItem::separator(app)?,
+            &item(app, "next_window", label(lang, "next_window"))?,
+            &item(app, "previous_window", label(lang, "previous_window"))?,
+        ],
+    )?;
+
+    let help_menu = Submenu::with_items(
+        app,
+        label(lang, "help"),
+        true,
+        &[
+            &MenuItem::with_id(app, "check_updates", label(lang, "check_updates"), true, None::<&str>)?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, "docs", label(lang, "docs"), true, None::<&str>)?,
+            &MenuItem::with_id(app, "feedback", label(lang, "feedback"), true, None::<&str>)?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, "about", label(lang, "about"), true, None::<&str>)?,
+        ],
+    )?;
+
+    if safe_mode {
+        help_menu.append_items(&[
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, "reset_settings", label(lang, "reset_settings"), true, None::<&str>)?,
+        ])?;
+    }
+
+    Menu::with_items(app, &[&file_menu, &edit_menu, &view_menu, &help_menu])
+}
+
+/// The menu last attached to the main window, kept so zen mode can put it back.
+#[derive(Default)]
+pub struct AppMenu(Mutex<Option<Menu<Wry>>>);
+
+pub fn current(app: &tauri::AppHandle) -> Option<Menu<Wry>> {
+    app.state::<AppMenu>().0.lock().unwrap().clone()
+}
+
+/// Attaches `menu` to the main window, replacing the previous one. In zen mode it stays hidden.
+pub fn attach(app: &tauri::AppHandle, menu: Menu<Wry>) -> tauri::Result<()> {
+    *app.state::<AppMenu>().0.lock().unwrap() = Some(menu.clone());
+    if let Some(main_window) = app.get_webview_window("main") {
+        main_window.set_menu(menu)?;
+        if window::is_zen(app, "main") {
+            main_window.hide_menu()?;
+        }
+    }
+    Ok(())
+}
+
+/// Builds a fresh menu from the current settings and attaches it to the main window.
+pub fn rebuild(app: &tauri::AppHandle) -> tauri::Result<()> {
+    attach(app, build_app_menu(app, &language(app))?)
+}
+
+/// Searches `items` and their submenus for the item with `id`.
+fn find(items: Vec<MenuItemKind<Wry>>, id: &str) -> Option<MenuItemKind<Wry>> {
+    for item in items {
+        if item.id() == id {
+            return Some(item);
+        }
+        if let Some(found) = item.as_submenu().and_then(|submenu| find(submenu.items().ok()?, id)) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// Greys out or re-enables a menu item, e.g. export while no document is open. Unknown ids
+/// are an error, so typos show up during development. The native editing items (undo, copy,
+/// …) follow the focused text field and can't be changed.
+#[tauri::command]
+pub async fn set_menu_item_enabled(app: tauri::AppHandle, id: String, enabled: bool) -> Result<(), String> {
+    let menu = current(&app).ok_or_else(|| format!("unknown menu item: {}", id))?;
+    let items = menu.items().map_err(|e| e.to_string())?;
+    let result = match find(items, &id) {
+        Some(MenuItemKind::MenuItem(item)) => item.set_enabled(enabled),
+        Some(MenuItemKind::Submenu(submenu)) => submenu.set_enabled(enabled),
+        Some(_) => return Err(format!("menu item can't be disabled: {}", id)),
+        None => return Err(format!("unknown menu item: {}", id)),
+    };
+    result.map_err(|e| e.to_string())?;
+
+    let state = app.state::<DisabledMenuItems>();
+    let mut disabled = state.0.lock().unwrap();
+    if enabled {
+        disabled.remove(&id);
+    } else {
+        disabled.insert(id);
+    }
+    Ok(())
+}
+
+/// Switches the menu bar to `lang` (`de` or `en`) and remembers it for the next launch.
+#[tauri::command]
+pub async fn set_menu_language(app: tauri::AppHandle, lang: String) -> Result<(), String> {
+    if !LANGUAGES.contains(&lang.as_str()) {
+        return Err(format!("unsupported language: {}", lang));
+    }
+    settings::set(&app, LANGUAGE_KEY, &lang)?;
+    rebuild(&app).map_err(|e| e.to_string())
+}
+
+pub fn handle_menu_event(window: &tauri::Window, event: MenuEvent) {
+    match event.id.as_ref() {
+        "new" => {
+            let _ = window.emit("menu-new", ());
+        }
+        "settings" => {
+            let _ = window.emit("menu-settings", ());
+        }
+        "print" => {
+            let _ = window.emit("menu-print", ());
+        }
+        "export_pdf" => {
+            let _ = window.emit("menu-export-pdf", ());
+        }
+        "export_docx" => {
+            let _ = window.emit("menu-export-docx", ());
+        }
+        "export_markdown" => {
+            let _ = window.emit("menu-export-markdown", ());
+        }
+        "reload" => {
+            if let Err(e) = webview::reload_frontend(window.app_handle()) {
+                log::error!("Soft reload failed: {}", e);
+            }
+        }
+        "fullscreen" => {
+            if let Ok(is_fullscreen) = window.is_fullscreen() {
+                let _ = window.set_fullscreen(!is_fullscreen);
+            }
+        }
+        "zoom_in" => {
+            let _ = window.emit("menu-zoom", "in");
+        }
+        "zoom_out" => {
+            let _ = window.emit("menu-zoom", "out");
+        }
+        "zoom_reset" => {
+            let _ = window.emit("menu-zoom", "reset");
+        }
+        "next_window" => {
+            let _ = window::cycle(window.app_handle(), "next");
+        }
+        "previous_window" => {
+            let _ = window::cycle(window.app_handle(), "prev");
+        }
+        "docs" => {
+            let _ = window.emit("menu-open-url", "https://gruenerator.de/");
+        }
+        "feedback" => {
+            let _ = window.emit("menu-open-url", "https://gitlab.com/Netzbegruenung/gruenerator/-/issues");
+        }
+        "about" => {
+            let _ = window.emit("menu-about", ());
+        }
+        "check_updates" => {
+            let _ = window.emit("menu-check-updates", ());
+        }
+        recent::CLEAR_ID => {
+            if let Err(e) = recent::clear(window.app_handle()) {
+                log::error!("Failed to clear recent documents: {}", e);
+            }
+            let _ = window.emit("menu-clear-recent", ());
+        }
+        "reset_settings" => {
+            if let Err(e) = safe_mode::reset(window.app_handle()) {
+                log::error!("Failed to reset settings: {}", e);
+            }
+        }
+        id => {
+            if let Some(doc_id) = id.strip_prefix(documents::PINNED_ID_PREFIX) {
+                let _ = window.emit("menu-open-pinned", doc_id);
+            } else if let Some(doc_id) = id.strip_prefix(recent::RECENT_ID_PREFIX) {
+                let _ = window.emit("menu-open-recent", doc_id);
+            }
+        }
+    }
+}
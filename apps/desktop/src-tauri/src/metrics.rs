@@ -0,0 +1,108 @@
+//! On-demand CPU and memory figures for the diagnostics panel. Nothing is sampled in the
+//! background: each call takes two process snapshots `MINIMUM_CPU_UPDATE_INTERVAL` apart
+//! (CPU usage is a delta) and returns.
+//!
+//! WebView2 (Windows) and WebKitGTK (Linux) run the webview in child processes of the app, so
+//! those are listed one by one. On macOS WKWebView's processes are XPC services owned by
+//! launchd rather than by us; there only the app process itself is reported. GPU usage isn't
+//! exposed per process by any of the platforms in a portable way and is left out.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+#[derive(Clone, Serialize)]
+pub struct ProcessUsage {
+    pid: u32,
+    name: String,
+    /// Percent of one core, so a busy process can exceed 100.
+    cpu_percent: f32,
+    memory_bytes: u64,
+}
+
+#[derive(Clone, Serialize)]
+pub struct ProcessMetrics {
+    app: ProcessUsage,
+    /// Webview and helper processes; empty unless `per_process` is set.
+    children: Vec<ProcessUsage>,
+    /// Sum over the app and its children.
+    total_cpu_percent: f32,
+    total_memory_bytes: u64,
+    /// Child processes could be attributed to the app on this platform.
+    per_process: bool,
+    cpu_count: usize,
+}
+
+fn usage(pid: Pid, process: &sysinfo::Process) -> ProcessUsage {
+    ProcessUsage {
+        pid: pid.as_u32(),
+        name: process.name().to_string_lossy().to_string(),
+        cpu_percent: process.cpu_usage(),
+        memory_bytes: process.memory(),
+    }
+}
+
+/// All transitive children of `root`, found by walking parent links.
+fn descendants(system: &System, root: Pid) -> Vec<Pid> {
+    let mut family = HashSet::from([root]);
+    loop {
+        let found: Vec<Pid> = system
+            .processes()
+            .iter()
+            .filter(|(pid, process)| {
+                !family.contains(*pid) && process.parent().is_some_and(|parent| family.contains(&parent))
+            })
+            .map(|(pid, _)| *pid)
+            .collect();
+        if found.is_empty() {
+            break;
+        }
+        family.extend(found);
+    }
+    family.remove(&root);
+    let mut pids: Vec<Pid> = family.into_iter().collect();
+    pids.sort();
+    pids
+}
+
+fn sample() -> Result<ProcessMetrics, String> {
+    let own_pid = sysinfo::get_current_pid().map_err(|e| e.to_string())?;
+    let refresh = ProcessRefreshKind::nothing().with_cpu().with_memory();
+
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh);
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh);
+
+    let app = system
+        .process(own_pid)
+        .map(|process| usage(own_pid, process))
+        .ok_or_else(|| "process_not_found".to_string())?;
+
+    let per_process = !cfg!(target_os = "macos");
+    let children: Vec<ProcessUsage> = if per_process {
+        descendants(&system, own_pid)
+            .into_iter()
+            .filter_map(|pid| system.process(pid).map(|process| usage(pid, process)))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    Ok(ProcessMetrics {
+        total_cpu_percent: app.cpu_percent + children.iter().map(|child| child.cpu_percent).sum::<f32>(),
+        total_memory_bytes: app.memory_bytes + children.iter().map(|child| child.memory_bytes).sum::<u64>(),
+        app,
+        children,
+        per_process,
+        cpu_count: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+    })
+}
+
+#[tauri::command]
+pub async fn get_process_metrics() -> Result<ProcessMetrics, String> {
+    tauri::async_runtime::spawn_blocking(sample)
+        .await
+        .map_err(|e| e.to_string())?
+}
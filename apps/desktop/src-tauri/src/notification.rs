@@ -0,0 +1,218 @@
+//! Native notifications that route back into the app, e.g. "Generation complete" while the
+//! window is in the background: clicking one brings the main window forward and emits
+//! `notification-clicked` with its `{ route }`. The notification plugin only fires and forgets
+//! on desktop, so the platform backends it wraps are used directly to learn about the click.
+//!
+//! Errors are `plugin_unavailable: notification`, and `notification_permission_denied` when
+//! the OS blocks the app's notifications, so the UI can point to the system settings
+//! (`open_notification_settings`). On Linux without a notification daemon the notification is
+//! handed to the frontend as `notification-fallback` (`{ title, body, route }`) instead.
+//!
+//! Reminders scheduled with `schedule_notification` wait in the running process, so they fire
+//! while the window is hidden to the tray but are lost when the app quits. Until then each is
+//! listed by `list_active_tasks` as a `notification` task, which `cancel_task` cancels.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde_json::json;
+use tauri::{Emitter, Manager};
+use tauri_plugin_notification::{NotificationExt, PermissionState};
+
+use crate::tasks::{TaskKind, TaskRegistry};
+use crate::{dnd, plugins};
+
+#[derive(Default)]
+struct Scheduled {
+    last_id: u32,
+    /// Dropping a reminder's sender wakes its thread without firing.
+    pending: HashMap<u32, mpsc::Sender<()>>,
+}
+
+/// Reminders that haven't fired yet.
+#[derive(Default)]
+pub struct ScheduledNotifications(Mutex<Scheduled>);
+
+fn clicked(app: &tauri::AppHandle, route: Option<String>) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+    let _ = app.emit("notification-clicked", json!({ "route": route }));
+}
+
+#[cfg(windows)]
+fn show(app: &tauri::AppHandle, title: String, body: String, route: Option<String>) -> Result<(), String> {
+    use tauri_winrt_notification::Toast;
+    use windows::core::HSTRING;
+    use windows::UI::Notifications::{NotificationSetting, ToastNotificationManager};
+
+    // Windows only shows toasts for app IDs it knows, which a dev build hasn't registered.
+    let app_id = if tauri::is_dev() {
+        Toast::POWERSHELL_APP_ID.to_string()
+    } else {
+        app.config().identifier.clone()
+    };
+    let setting = ToastNotificationManager::CreateToastNotifierWithId(&HSTRING::from(&app_id))
+        .and_then(|notifier| notifier.Setting())
+        .map_err(|e| e.to_string())?;
+    if setting != NotificationSetting::Enabled {
+        return Err("notification_permission_denied".to_string());
+    }
+
+    let app = app.clone();
+    Toast::new(&app_id)
+        .title(&title)
+        .text1(&body)
+        .on_activated(move |_| {
+            clicked(&app, route.clone());
+            Ok(())
+        })
+        .show()
+        .map_err(|e| e.to_string())
+}
+
+/// Waiting for the click blocks, so it happens on a thread of its own that ends once the
+/// notification is clicked or dismissed.
+#[cfg(target_os = "macos")]
+fn show(app: &tauri::AppHandle, title: String, body: String, route: Option<String>) -> Result<(), String> {
+    use mac_notification_sys::{Notification, NotificationResponse};
+
+    // Set once per process; the plugin may have done so already.
+    let _ = mac_notification_sys::set_application(if tauri::is_dev() {
+        "com.apple.Terminal"
+    } else {
+        &app.config().identifier
+    });
+    let app = app.clone();
+    std::thread::spawn(move || {
+        match Notification::new().title(&title).message(&body).wait_for_click(true).send() {
+            Ok(NotificationResponse::Click) => clicked(&app, route),
+            Ok(_) => {}
+            Err(e) => log::warn!("Failed to show notification: {}", e),
+        }
+    });
+    Ok(())
+}
+
+/// Daemons report a click on the notification itself as the `default` action.
+#[cfg(not(any(windows, target_os = "macos")))]
+fn show(app: &tauri::AppHandle, title: String, body: String, route: Option<String>) -> Result<(), String> {
+    let mut notification = notify_rust::Notification::new();
+    notification.summary(&title).body(&body).auto_icon().action("default", "Öffnen");
+    match notification.show() {
+        Ok(handle) => {
+            let app = app.clone();
+            std::thread::spawn(move || {
+                handle.wait_for_action(|action| {
+                    if action == "default" {
+                        clicked(&app, route);
+                    }
+                })
+            });
+        }
+        Err(e) => {
+            log::warn!("No notification daemon, passing the notification to the frontend: {}", e);
+            let _ = app.emit(
+                "notification-fallback",
+                json!({ "title": title, "body": body, "route": route }),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Drops the pending reminder `id`, which wakes its thread without firing. Returns whether it
+/// was still pending.
+fn cancel(app: &tauri::AppHandle, id: u32) -> bool {
+    let scheduled = app.state::<ScheduledNotifications>();
+    let removed = scheduled.0.lock().unwrap().pending.remove(&id).is_some();
+    removed
+}
+
+fn ensure_allowed(app: &tauri::AppHandle) -> Result<(), String> {
+    plugins::require(app, plugins::NOTIFICATION)?;
+    if !matches!(app.notification().permission_state(), Ok(PermissionState::Granted)) {
+        return Err("notification_permission_denied".to_string());
+    }
+    Ok(())
+}
+
+/// Shows a native notification; a click emits `notification-clicked` with `route`. Nothing is
+/// shown while "Nicht stören" is on.
+#[tauri::command]
+pub async fn show_notification(
+    app: tauri::AppHandle,
+    title: String,
+    body: String,
+    route: Option<String>,
+) -> Result<(), String> {
+    ensure_allowed(&app)?;
+    if dnd::is_enabled(&app) {
+        log::debug!("Notification suppressed by do not disturb: {}", title);
+        return Ok(());
+    }
+    show(&app, title, body, route)
+}
+
+/// Shows a notification after `delay_secs`, e.g. "Veröffentliche den Post um 9 Uhr". Returns
+/// the id for `cancel_notification`. "Nicht stören" is checked when the reminder fires; one
+/// that comes due while it is on is dropped.
+#[tauri::command]
+pub async fn schedule_notification(
+    app: tauri::AppHandle,
+    title: String,
+    body: String,
+    delay_secs: u64,
+) -> Result<u32, String> {
+    ensure_allowed(&app)?;
+    let (cancel_tx, cancel_rx) = mpsc::channel::<()>();
+    let id = {
+        let scheduled = app.state::<ScheduledNotifications>();
+        let mut scheduled = scheduled.0.lock().unwrap();
+        scheduled.last_id = scheduled.last_id.wrapping_add(1).max(1);
+        let id = scheduled.last_id;
+        scheduled.pending.insert(id, cancel_tx);
+        id
+    };
+    log::info!("Reminder {} scheduled in {}s", id, delay_secs);
+    let task = app
+        .state::<TaskRegistry>()
+        .start(&app, TaskKind::Notification, title.clone(), true);
+    let cancel_app = app.clone();
+    task.on_cancel(move || {
+        cancel(&cancel_app, id);
+    });
+
+    std::thread::spawn(move || {
+        // Keeps the task listed until the reminder fires or is cancelled.
+        let _task = task;
+        if cancel_rx.recv_timeout(Duration::from_secs(delay_secs)) != Err(RecvTimeoutError::Timeout) {
+            return;
+        }
+        // Claims the reminder, unless it was cancelled between the timeout and here.
+        if !cancel(&app, id) {
+            return;
+        }
+        if dnd::is_enabled(&app) {
+            log::debug!("Reminder {} suppressed by do not disturb", id);
+            return;
+        }
+        if let Err(e) = show(&app, title, body, None) {
+            log::warn!("Failed to show reminder {}: {}", id, e);
+        }
+    });
+    Ok(id)
+}
+
+/// Cancels a pending reminder. Ids that already fired (or never existed) are ignored.
+#[tauri::command]
+pub async fn cancel_notification(app: tauri::AppHandle, id: u32) -> Result<(), String> {
+    if cancel(&app, id) {
+        log::info!("Reminder {} cancelled", id);
+    }
+    Ok(())
+}
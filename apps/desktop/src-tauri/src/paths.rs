@@ -0,0 +1,93 @@
+//! Where the app keeps its files. A `portable.txt` next to the executable moves everything
+//! (store, cache, logs, window state) into a `data` folder beside it, so the app can run from
+//! a USB stick. Without the marker, or if that folder isn't writable, the OS app dirs are used.
+
+use std::path::{Path, PathBuf};
+
+use tauri::Manager;
+
+pub const PORTABLE_MARKER: &str = "portable.txt";
+const PORTABLE_DIR: &str = "data";
+
+#[derive(Default)]
+pub struct Portable {
+    root: Option<PathBuf>,
+    /// Why a present marker was ignored, logged once the logger is up.
+    problem: Option<String>,
+}
+
+impl Portable {
+    /// Runs before the builder is set up, since plugin paths (logs, window state) depend on it.
+    pub fn detect() -> Self {
+        let Some(exe_dir) = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        else {
+            return Self::default();
+        };
+        if !exe_dir.join(PORTABLE_MARKER).is_file() {
+            return Self::default();
+        }
+
+        let root = exe_dir.join(PORTABLE_DIR);
+        match check_writable(&root) {
+            Ok(()) => Self { root: Some(root), problem: None },
+            Err(e) => Self {
+                root: None,
+                problem: Some(format!("{} is not writable: {}", root.display(), e)),
+            },
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.root.is_some()
+    }
+
+    pub fn dir(&self, name: &str) -> Option<PathBuf> {
+        self.root.as_ref().map(|root| root.join(name))
+    }
+}
+
+pub(crate) fn check_writable(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let probe = dir.join(".write-test");
+    std::fs::write(&probe, b"")?;
+    std::fs::remove_file(probe)
+}
+
+pub fn log_mode(app: &tauri::AppHandle) {
+    let portable = app.state::<Portable>();
+    if let Some(root) = &portable.root {
+        log::info!("Portable mode, data in {}", root.display());
+    } else if let Some(problem) = &portable.problem {
+        log::warn!("Ignoring {}: {}", PORTABLE_MARKER, problem);
+    }
+}
+
+fn resolve(
+    app: &tauri::AppHandle,
+    name: &str,
+    fallback: impl FnOnce() -> tauri::Result<PathBuf>,
+) -> Result<PathBuf, String> {
+    match app.try_state::<Portable>().and_then(|portable| portable.dir(name)) {
+        Some(dir) => Ok(dir),
+        None => fallback().map_err(|e| e.to_string()),
+    }
+}
+
+pub fn data_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    resolve(app, "app-data", || app.path().app_data_dir())
+}
+
+pub fn config_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    resolve(app, "config", || app.path().app_config_dir())
+}
+
+pub fn cache_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    resolve(app, "cache", || app.path().app_cache_dir())
+}
+
+#[tauri::command]
+pub async fn is_portable(app: tauri::AppHandle) -> Result<bool, String> {
+    Ok(app.state::<Portable>().is_active())
+}
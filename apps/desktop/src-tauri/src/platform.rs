@@ -0,0 +1,74 @@
+use serde::Serialize;
+
+use crate::startup;
+
+#[derive(Clone, Serialize)]
+struct OsUnsupported {
+    os: String,
+    detected: String,
+    minimum: String,
+}
+
+/// Oldest releases the WebView, tray and vibrancy integrations are known to work on.
+fn minimum_version(os: os_info::Type) -> Option<(u64, u64)> {
+    match os {
+        os_info::Type::Windows => Some((10, 0)),
+        os_info::Type::Macos => Some((11, 0)),
+        _ => None,
+    }
+}
+
+/// Warns (never blocks) when running on an OS release below the supported minimum.
+pub fn check_os_version(app: &tauri::AppHandle) {
+    let info = os_info::get();
+    let Some((min_major, min_minor)) = minimum_version(info.os_type()) else {
+        return;
+    };
+    let os_info::Version::Semantic(major, minor, _) = *info.version() else {
+        return;
+    };
+
+    if (major, minor) < (min_major, min_minor) {
+        log::warn!("Unsupported OS version: {} {}", info.os_type(), info.version());
+        startup::notify(
+            app,
+            "os-unsupported",
+            OsUnsupported {
+                os: info.os_type().to_string(),
+                detected: info.version().to_string(),
+                minimum: format!("{}.{}", min_major, min_minor),
+            },
+        );
+    }
+}
+
+#[derive(Clone, Serialize)]
+struct ArchitectureMismatch {
+    binary: &'static str,
+    native: &'static str,
+}
+
+/// The host's native architecture when this binary runs translated (e.g. the Intel build
+/// under Rosetta on Apple Silicon), `None` when it runs natively.
+#[cfg(target_os = "macos")]
+fn translated_native_arch() -> Option<&'static str> {
+    let output = std::process::Command::new("sysctl")
+        .args(["-n", "sysctl.proc_translated"])
+        .output()
+        .ok()?;
+    (String::from_utf8_lossy(&output.stdout).trim() == "1").then_some("aarch64")
+}
+
+#[cfg(not(target_os = "macos"))]
+fn translated_native_arch() -> Option<&'static str> {
+    None
+}
+
+/// Informational only: suggests the native build when running under emulation.
+pub fn check_architecture(app: &tauri::AppHandle) {
+    if let Some(native) = translated_native_arch() {
+        let binary = std::env::consts::ARCH;
+        log::warn!("Running {} build on a {} host via emulation", binary, native);
+        startup::notify(app, "architecture-mismatch", ArchitectureMismatch { binary, native });
+    }
+}
@@ -0,0 +1,120 @@
+//! Optional plugins are registered from `setup` instead of on the builder, where a failing
+//! plugin aborts the whole launch (some Linux setups can't bring up e.g. the notification
+//! backend). A plugin that fails here is logged, reported as `plugin-unavailable` and left
+//! out; the features built on it check [`require`] and fail with `plugin_unavailable: <name>`.
+//!
+//! Registering late means the plugin misses the windows created from the config. [`Late`]
+//! replays those to it and runs its initialization script in their webviews after each load.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Mutex;
+
+use serde_json::json;
+use tauri::plugin::Plugin;
+use tauri::webview::PageLoadEvent;
+use tauri::{Manager, Runtime};
+
+use crate::startup;
+
+pub const UPDATER: &str = "updater";
+pub const STORE: &str = "store";
+pub const NOTIFICATION: &str = "notification";
+pub const WINDOW_STATE: &str = "window-state";
+
+/// Optional plugins and whether they came up; a plugin that was never registered (e.g.
+/// window-state in safe mode) is absent.
+#[derive(Default)]
+pub struct Plugins(Mutex<BTreeMap<&'static str, bool>>);
+
+struct Late<P> {
+    inner: P,
+    /// Webviews that existed before the plugin and so never got its initialization script.
+    existing: HashSet<String>,
+}
+
+impl<R: Runtime, P: Plugin<R>> Plugin<R> for Late<P> {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn initialize(
+        &mut self,
+        app: &tauri::AppHandle<R>,
+        config: serde_json::Value,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.inner.initialize(app, config)?;
+        let script = self.inner.initialization_script();
+        for (label, window) in app.webview_windows() {
+            self.inner.window_created(window.as_ref().window());
+            self.inner.webview_created(window.as_ref().clone());
+            if let Some(script) = &script {
+                let _ = window.eval(script);
+                self.existing.insert(label);
+            }
+        }
+        Ok(())
+    }
+
+    fn initialization_script(&self) -> Option<String> {
+        self.inner.initialization_script()
+    }
+
+    fn window_created(&mut self, window: tauri::Window<R>) {
+        self.inner.window_created(window)
+    }
+
+    fn webview_created(&mut self, webview: tauri::Webview<R>) {
+        self.inner.webview_created(webview)
+    }
+
+    fn on_navigation(&mut self, webview: &tauri::Webview<R>, url: &url::Url) -> bool {
+        self.inner.on_navigation(webview, url)
+    }
+
+    fn on_page_load(&mut self, webview: &tauri::Webview<R>, payload: &tauri::webview::PageLoadPayload<'_>) {
+        if payload.event() == PageLoadEvent::Finished && self.existing.contains(webview.label()) {
+            if let Some(script) = self.inner.initialization_script() {
+                let _ = webview.eval(script);
+            }
+        }
+        self.inner.on_page_load(webview, payload)
+    }
+
+    fn on_event(&mut self, app: &tauri::AppHandle<R>, event: &tauri::RunEvent) {
+        self.inner.on_event(app, event)
+    }
+
+    fn extend_api(&mut self, invoke: tauri::ipc::Invoke<R>) -> bool {
+        self.inner.extend_api(invoke)
+    }
+}
+
+/// Registers `plugin` as the optional plugin `name`.
+pub fn register<P: Plugin<tauri::Wry> + 'static>(app: &tauri::AppHandle, name: &'static str, plugin: P) {
+    let result = app.plugin(Late {
+        inner: plugin,
+        existing: HashSet::new(),
+    });
+    if let Err(e) = &result {
+        log::error!("Plugin {} failed to initialize, continuing without it: {}", name, e);
+        startup::notify(app, "plugin-unavailable", json!({ "plugin": name, "error": e.to_string() }));
+    }
+    app.state::<Plugins>().0.lock().unwrap().insert(name, result.is_ok());
+}
+
+pub fn is_available(app: &tauri::AppHandle, name: &str) -> bool {
+    app.try_state::<Plugins>()
+        .is_some_and(|plugins| plugins.0.lock().unwrap().get(name).copied().unwrap_or(false))
+}
+
+pub fn require(app: &tauri::AppHandle, name: &str) -> Result<(), String> {
+    if is_available(app, name) {
+        Ok(())
+    } else {
+        Err(format!("plugin_unavailable: {}", name))
+    }
+}
+
+pub fn status(app: &tauri::AppHandle) -> BTreeMap<&'static str, bool> {
+    app.state::<Plugins>().0.lock().unwrap().clone()
+}
@@ -0,0 +1,256 @@
+//! Printing a finished text. The HTML is loaded into a hidden window of its own, so the print
+//! shows the document rather than the app around it, and the platform print dialog is opened
+//! for that window. Brand fonts are embedded into the page, as the print window doesn't load
+//! the frontend's stylesheets.
+//!
+//! Errors are `printing_cancelled` when the user dismissed the dialog and `print_failed: …`
+//! otherwise.
+
+use std::sync::mpsc;
+use std::time::Duration;
+
+use base64::Engine;
+use tauri::webview::PageLoadEvent;
+use tauri::{Manager, WebviewUrl, WebviewWindowBuilder};
+
+pub const WINDOW_LABEL: &str = "print";
+const LOAD_TIMEOUT: Duration = Duration::from_secs(15);
+/// Longest URL WebView2 navigates to.
+const MAX_DATA_URL_LEN: usize = 2 * 1024 * 1024;
+
+const FONTS: &[(&str, &str, &str, &[u8])] = &[
+    ("PT Sans", "normal", "normal", include_bytes!("../../../web/public/fonts/PTSans-Regular.woff2")),
+    ("PT Sans", "bold", "normal", include_bytes!("../../../web/public/fonts/PTSans-Bold.woff2")),
+    ("PT Sans", "normal", "italic", include_bytes!("../../../web/public/fonts/PTSans-Italic.woff2")),
+    ("GrueneType", "normal", "normal", include_bytes!("../../../web/public/fonts/GrueneType.woff2")),
+];
+
+/// `html` as a standalone page with the brand fonts and an explicit UTF-8 charset, so umlauts
+/// survive regardless of the platform's default encoding.
+fn document(html: &str) -> String {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let faces: String = FONTS
+        .iter()
+        .map(|(family, weight, style, data)| {
+            format!(
+                "@font-face{{font-family:'{}';font-weight:{};font-style:{};src:url(data:font/woff2;base64,{}) format('woff2');}}",
+                family,
+                weight,
+                style,
+                engine.encode(data)
+            )
+        })
+        .collect();
+    format!(
+        "<!DOCTYPE html><html lang=\"de\"><head><meta charset=\"utf-8\"><style>{}\
+         body{{font-family:'PT Sans',sans-serif;font-size:11pt;line-height:1.4;margin:0}}\
+         h1,h2,h3{{font-family:'GrueneType','PT Sans',sans-serif;font-weight:normal}}\
+         @page{{margin:20mm}}</style></head><body>{}</body></html>",
+        faces, html
+    )
+}
+
+fn failed(e: impl std::fmt::Display) -> String {
+    format!("print_failed: {}", e)
+}
+
+/// Opens the print dialog for `print_window` and reports the outcome on `done`.
+fn run_dialog(print_window: &tauri::WebviewWindow, owner: &tauri::Window, done: mpsc::Sender<Result<(), String>>) {
+    #[cfg(windows)]
+    {
+        // Window handles aren't `Send`; the address is.
+        let owner = match owner.hwnd() {
+            Ok(hwnd) => hwnd.0 as isize,
+            Err(e) => {
+                let _ = done.send(Err(failed(e)));
+                return;
+            }
+        };
+        let sender = done.clone();
+        let result = print_window.with_webview(move |webview| {
+            let owner = windows::Win32::Foundation::HWND(owner as *mut _);
+            if let Err(e) = unsafe { windows_print(webview, owner, sender.clone()) } {
+                let _ = sender.send(Err(failed(e)));
+            }
+        });
+        if let Err(e) = result {
+            let _ = done.send(Err(failed(e)));
+        }
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let _ = owner;
+        let sender = done.clone();
+        let result = print_window.with_webview(move |webview| {
+            let _ = sender.send(unsafe { macos_print(webview) });
+        });
+        if let Err(e) = result {
+            let _ = done.send(Err(failed(e)));
+        }
+    }
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        // WebKitGTK doesn't report whether the dialog was cancelled.
+        let _ = owner;
+        let _ = done.send(print_window.print().map_err(failed));
+    }
+}
+
+/// The system print dialog, then a print of the webview with the chosen printer, copies,
+/// collation and orientation.
+#[cfg(windows)]
+unsafe fn windows_print(
+    webview: tauri::webview::PlatformWebview,
+    owner: windows::Win32::Foundation::HWND,
+    done: mpsc::Sender<Result<(), String>>,
+) -> windows::core::Result<()> {
+    use webview2_com::Microsoft::Web::WebView2::Win32::{
+        ICoreWebView2Environment6, ICoreWebView2PrintSettings2, ICoreWebView2_16, ICoreWebView2_2,
+        COREWEBVIEW2_PRINT_COLLATION_COLLATED, COREWEBVIEW2_PRINT_COLLATION_UNCOLLATED,
+        COREWEBVIEW2_PRINT_ORIENTATION_LANDSCAPE, COREWEBVIEW2_PRINT_STATUS_PRINTER_UNAVAILABLE,
+        COREWEBVIEW2_PRINT_STATUS_SUCCEEDED,
+    };
+    use webview2_com::PrintCompletedHandler;
+    use windows::core::{Interface, HSTRING, PCWSTR};
+    use windows::Win32::Foundation::GlobalFree;
+    use windows::Win32::Graphics::Gdi::{DEVMODEW, DMORIENT_LANDSCAPE};
+    use windows::Win32::System::Memory::{GlobalLock, GlobalUnlock};
+    use windows::Win32::UI::Controls::Dialogs::{
+        PrintDlgExW, DEVNAMES, PD_COLLATE, PD_NOCURRENTPAGE, PD_NOPAGENUMS, PD_NOSELECTION, PD_RESULT_PRINT,
+        PRINTDLGEXW, START_PAGE_GENERAL,
+    };
+
+    let mut dialog = PRINTDLGEXW {
+        lStructSize: std::mem::size_of::<PRINTDLGEXW>() as u32,
+        hwndOwner: owner,
+        Flags: PD_NOPAGENUMS | PD_NOSELECTION | PD_NOCURRENTPAGE,
+        nCopies: 1,
+        nStartPage: START_PAGE_GENERAL,
+        ..Default::default()
+    };
+    PrintDlgExW(&mut dialog)?;
+
+    let mut printer = String::new();
+    if !dialog.hDevNames.is_invalid() {
+        let names = GlobalLock(dialog.hDevNames) as *const DEVNAMES;
+        if !names.is_null() {
+            let name = (names as *const u16).add((*names).wDeviceOffset as usize);
+            printer = PCWSTR(name).to_string().unwrap_or_default();
+            let _ = GlobalUnlock(dialog.hDevNames);
+        }
+        let _ = GlobalFree(Some(dialog.hDevNames));
+    }
+    let mut landscape = false;
+    if !dialog.hDevMode.is_invalid() {
+        let mode = GlobalLock(dialog.hDevMode) as *const DEVMODEW;
+        if !mode.is_null() {
+            landscape = (*mode).Anonymous1.Anonymous1.dmOrientation as u32 == DMORIENT_LANDSCAPE;
+            let _ = GlobalUnlock(dialog.hDevMode);
+        }
+        let _ = GlobalFree(Some(dialog.hDevMode));
+    }
+    // `PD_RESULT_APPLY` is "Übernehmen" followed by "Abbrechen".
+    if dialog.dwResultAction != PD_RESULT_PRINT {
+        let _ = done.send(Err("printing_cancelled".to_string()));
+        return Ok(());
+    }
+
+    let core = webview.controller().CoreWebView2()?;
+    let environment = core.cast::<ICoreWebView2_2>()?.Environment()?.cast::<ICoreWebView2Environment6>()?;
+    let settings = environment.CreatePrintSettings()?;
+    if landscape {
+        settings.SetOrientation(COREWEBVIEW2_PRINT_ORIENTATION_LANDSCAPE)?;
+    }
+    let options = settings.cast::<ICoreWebView2PrintSettings2>()?;
+    let printer = HSTRING::from(printer);
+    options.SetPrinterName(PCWSTR(printer.as_ptr()))?;
+    options.SetCopies(dialog.nCopies.max(1) as i32)?;
+    options.SetCollation(if dialog.Flags.contains(PD_COLLATE) {
+        COREWEBVIEW2_PRINT_COLLATION_COLLATED
+    } else {
+        COREWEBVIEW2_PRINT_COLLATION_UNCOLLATED
+    })?;
+
+    let handler = PrintCompletedHandler::create(Box::new(move |result, status| {
+        let outcome = match result {
+            Err(e) => Err(failed(e)),
+            Ok(()) if status == COREWEBVIEW2_PRINT_STATUS_SUCCEEDED => Ok(()),
+            Ok(()) if status == COREWEBVIEW2_PRINT_STATUS_PRINTER_UNAVAILABLE => Err(failed("printer unavailable")),
+            Ok(()) => Err(failed(format!("status {}", status.0))),
+        };
+        let _ = done.send(outcome);
+        Ok(())
+    }));
+    core.cast::<ICoreWebView2_16>()?.Print(&settings, &handler)
+}
+
+/// The print panel, run modally. Failures are reported by the print system's own alert, so a
+/// `false` from `runOperation` means the panel was dismissed.
+#[cfg(target_os = "macos")]
+unsafe fn macos_print(webview: tauri::webview::PlatformWebview) -> Result<(), String> {
+    use objc2_app_kit::NSPrintInfo;
+    use objc2_web_kit::WKWebView;
+
+    let view = &*(webview.inner() as *const WKWebView);
+    let operation = view.printOperationWithPrintInfo(&NSPrintInfo::sharedPrintInfo());
+    operation.setShowsPrintPanel(true);
+    operation.setShowsProgressPanel(true);
+    if operation.runOperation() {
+        Ok(())
+    } else {
+        Err("printing_cancelled".to_string())
+    }
+}
+
+/// Prints `html` (the rendered text) through the platform print dialog, owned by `window`.
+/// The hidden print window is closed again whatever the outcome; one print runs at a time.
+#[tauri::command]
+pub async fn print_content(window: tauri::Window, html: String) -> Result<(), String> {
+    if window.app_handle().get_webview_window(WINDOW_LABEL).is_some() {
+        return Err("print_in_progress".to_string());
+    }
+    let engine = base64::engine::general_purpose::STANDARD;
+    let url = format!("data:text/html;charset=utf-8;base64,{}", engine.encode(document(&html)));
+    if url.len() > MAX_DATA_URL_LEN {
+        return Err("content_too_large".to_string());
+    }
+    let url = url.parse().map_err(failed)?;
+
+    let (loaded_tx, loaded_rx) = mpsc::channel();
+    let print_window = WebviewWindowBuilder::new(window.app_handle(), WINDOW_LABEL, WebviewUrl::External(url))
+        .title("Drucken")
+        .inner_size(794.0, 1123.0)
+        .visible(false)
+        .skip_taskbar(true)
+        .on_page_load(move |_, payload| {
+            if payload.event() == PageLoadEvent::Finished {
+                let _ = loaded_tx.send(());
+            }
+        })
+        .build()
+        .map_err(failed)?;
+
+    let result = async {
+        tauri::async_runtime::spawn_blocking(move || loaded_rx.recv_timeout(LOAD_TIMEOUT))
+            .await
+            .map_err(failed)?
+            .map_err(|_| failed("page did not load"))?;
+
+        let (done_tx, done_rx) = mpsc::channel();
+        run_dialog(&print_window, &window, done_tx);
+        tauri::async_runtime::spawn_blocking(move || done_rx.recv())
+            .await
+            .map_err(failed)?
+            .map_err(|_| failed("no result"))?
+    }
+    .await;
+
+    if let Err(e) = print_window.destroy() {
+        log::warn!("Failed to close print window: {}", e);
+    }
+    match &result {
+        Err(e) if e != "printing_cancelled" => log::error!("Printing failed: {}", e),
+        _ => {}
+    }
+    result
+}
@@ -0,0 +1,74 @@
+//! Launcher-style quick capture: a global hotkey opens a small borderless input window; Enter
+//! hands the prompt to the main window as `quick-capture-submitted`, Escape or clicking
+//! elsewhere dismisses it. The window skips the taskbar and the saved window state, so it
+//! never shows up in window lists or session restore.
+
+use serde_json::json;
+use tauri::{Emitter, Manager, WebviewUrl, WebviewWindowBuilder};
+
+pub const WINDOW_LABEL: &str = "quick-capture";
+pub const SHORTCUT_ID: &str = "quick_capture";
+
+pub fn open(app: &tauri::AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(WINDOW_LABEL) {
+        let _ = window.show();
+        return window.set_focus().map_err(|e| e.to_string());
+    }
+
+    let window = WebviewWindowBuilder::new(app, WINDOW_LABEL, WebviewUrl::App("quick-capture.html".into()))
+        .title("Grünerator")
+        .inner_size(560.0, 64.0)
+        .resizable(false)
+        .decorations(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .center()
+        .focused(true)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let window_clone = window.clone();
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::Focused(false) = event {
+            let _ = window_clone.close();
+        }
+    });
+    Ok(())
+}
+
+fn close(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window(WINDOW_LABEL) {
+        let _ = window.close();
+    }
+}
+
+#[tauri::command]
+pub async fn open_quick_capture(app: tauri::AppHandle) -> Result<(), String> {
+    open(&app)
+}
+
+#[tauri::command]
+pub async fn close_quick_capture(app: tauri::AppHandle) -> Result<(), String> {
+    close(&app);
+    Ok(())
+}
+
+/// Dismisses the capture window and brings up the main window to generate from `prompt`.
+#[tauri::command]
+pub async fn submit_quick_capture(app: tauri::AppHandle, prompt: String) -> Result<(), String> {
+    let prompt = prompt.trim();
+    if prompt.is_empty() {
+        return Err("empty_prompt".to_string());
+    }
+    close(&app);
+
+    let main_window = app
+        .get_webview_window("main")
+        .ok_or_else(|| "main window not found".to_string())?;
+    let _ = main_window.unminimize();
+    let _ = main_window.show();
+    let _ = main_window.set_focus();
+    main_window
+        .emit("quick-capture-submitted", json!({ "prompt": prompt }))
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,161 @@
+//! Recently opened documents, newest first, for the Datei menu and the start screen. Titles
+//! aren't unique (every other draft is called "Rede"), so entries with the same title get a
+//! display label that tells them apart: their source if that differs, else the date they were
+//! opened. Only the label is adjusted; ids and titles are stored as reported.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{documents, locale, settings};
+
+const RECENT_KEY: &str = "recent_documents";
+const MAX_RECENT: usize = 10;
+
+pub const RECENT_ID_PREFIX: &str = "recent:";
+pub const CLEAR_ID: &str = "recent_clear";
+
+#[derive(Clone, Serialize, Deserialize)]
+struct StoredDocument {
+    id: String,
+    title: String,
+    /// Where the document came from, e.g. a template or a shared link.
+    #[serde(default)]
+    source: Option<String>,
+    opened_at: u64,
+}
+
+#[derive(Clone, Serialize)]
+pub struct RecentDocument {
+    pub id: String,
+    pub title: String,
+    pub source: Option<String>,
+    pub opened_at: u64,
+    /// Unique among the list, for menus and the start screen.
+    pub label: String,
+}
+
+/// The stored list with duplicate ids merged into their most recent entry.
+fn stored(app: &tauri::AppHandle) -> Vec<StoredDocument> {
+    let mut documents: Vec<StoredDocument> = settings::get(app, RECENT_KEY).unwrap_or_default();
+    let mut seen = Vec::new();
+    documents.retain(|doc| {
+        if seen.contains(&doc.id) {
+            return false;
+        }
+        seen.push(doc.id.clone());
+        true
+    });
+    documents
+}
+
+fn labels(app: &tauri::AppHandle, documents: &[StoredDocument]) -> Vec<String> {
+    let titles: Vec<String> = documents.iter().map(|doc| documents::menu_label(&doc.title)).collect();
+    let locale = locale::current(app);
+    let opened = |doc: &StoredDocument, style| {
+        locale::format_datetime_in(locale, doc.opened_at as i64, style).unwrap_or_default()
+    };
+
+    documents
+        .iter()
+        .zip(&titles)
+        .map(|(doc, title)| {
+            let same_title: Vec<&StoredDocument> = documents
+                .iter()
+                .zip(&titles)
+                .filter(|(_, other)| other.to_lowercase() == title.to_lowercase())
+                .map(|(other, _)| other)
+                .collect();
+            if same_title.len() == 1 {
+                return title.clone();
+            }
+            let unique_source = doc
+                .source
+                .as_ref()
+                .filter(|source| same_title.iter().filter(|other| other.source.as_ref() == Some(source)).count() == 1);
+            if let Some(source) = unique_source {
+                return format!("{} ({})", title, source);
+            }
+            let date = opened(doc, "date");
+            let same_day = same_title.iter().filter(|other| opened(other, "date") == date).count();
+            let when = if same_day == 1 { date } else { opened(doc, "datetime") };
+            format!("{} ({})", title, when)
+        })
+        .collect()
+}
+
+pub fn recent(app: &tauri::AppHandle) -> Vec<RecentDocument> {
+    let documents = stored(app);
+    let labels = labels(app, &documents);
+    documents
+        .into_iter()
+        .zip(labels)
+        .map(|(doc, label)| RecentDocument {
+            id: doc.id,
+            title: doc.title,
+            source: doc.source,
+            opened_at: doc.opened_at,
+            label,
+        })
+        .collect()
+}
+
+/// Moves the document to the top of the list, replacing an earlier entry with the same id.
+fn push(app: &tauri::AppHandle, id: String, title: &str, source: Option<String>) -> Result<(), String> {
+    documents::validate_id(&id)?;
+    if title.trim().is_empty() {
+        return Err("title is required".to_string());
+    }
+    let source = source
+        .map(|source| source.trim().to_string())
+        .filter(|source| !source.is_empty());
+
+    let mut recent = stored(app);
+    recent.retain(|doc| doc.id != id);
+    recent.insert(
+        0,
+        StoredDocument {
+            id,
+            title: title.trim().to_string(),
+            source,
+            opened_at: crate::now_millis(),
+        },
+    );
+    recent.truncate(MAX_RECENT);
+    settings::set(app, RECENT_KEY, &recent)?;
+    documents::refresh_menus(app)
+}
+
+/// Empties the list, for "Liste leeren" in the Datei menu.
+pub fn clear(app: &tauri::AppHandle) -> Result<(), String> {
+    settings::set(app, RECENT_KEY, Vec::<StoredDocument>::new())?;
+    documents::refresh_menus(app)
+}
+
+#[tauri::command]
+pub async fn add_recent_document(
+    app: tauri::AppHandle,
+    id: String,
+    title: String,
+    source: Option<String>,
+) -> Result<(), String> {
+    push(&app, id, &title, source)
+}
+
+/// `add_recent_document` without a source, for callers that only have a menu label.
+#[tauri::command]
+pub async fn push_recent_file(app: tauri::AppHandle, id: String, label: String) -> Result<(), String> {
+    push(&app, id, &label, None)
+}
+
+#[tauri::command]
+pub async fn remove_recent_document(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    let mut recent = stored(&app);
+    recent.retain(|doc| doc.id != id);
+    settings::set(&app, RECENT_KEY, &recent)?;
+    documents::refresh_menus(&app)
+}
+
+/// Newest first, with disambiguated labels.
+#[tauri::command]
+pub async fn get_recent_documents(app: tauri::AppHandle) -> Result<Vec<RecentDocument>, String> {
+    Ok(recent(&app))
+}
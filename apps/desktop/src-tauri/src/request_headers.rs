@@ -0,0 +1,223 @@
+//! Extra HTTP headers sent with the webview's requests to the backend, for backends that expect
+//! an instance identifier or CSRF header (self-hosted deployments). Headers are persisted and
+//! attached to each webview via [`attach`] when it is created. Requests to other origins (CDNs,
+//! the SSO provider, fonts) never get them.
+//!
+//! Only WebView2 (Windows) lets the host rewrite outgoing requests, so that is the only
+//! platform where the headers are actually applied; WKWebView and WebKitGTK have no such hook
+//! and [`is_applied`] reports `false` there.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+
+use crate::settings;
+
+const HEADERS_KEY: &str = "request_headers";
+const MAX_HEADERS: usize = 16;
+const MAX_VALUE_LEN: usize = 1024;
+/// The backend the headers are meant for. Self-hosted builds set `GRUENERATOR_API_ORIGIN` at
+/// compile time, matching the web build's `VITE_API_BASE_URL`.
+#[cfg(windows)]
+const API_ORIGIN: &str = match option_env!("GRUENERATOR_API_ORIGIN") {
+    Some(origin) => origin,
+    None => "https://gruenerator.eu",
+};
+
+/// Headers owned by the browser engine (Fetch spec "forbidden request headers"); overriding
+/// them breaks requests or is silently ignored.
+const FORBIDDEN: &[&str] = &[
+    "accept-charset",
+    "accept-encoding",
+    "access-control-request-headers",
+    "access-control-request-method",
+    "connection",
+    "content-length",
+    "cookie",
+    "cookie2",
+    "date",
+    "dnt",
+    "expect",
+    "host",
+    "keep-alive",
+    "origin",
+    "referer",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "via",
+];
+const FORBIDDEN_PREFIXES: &[&str] = &["proxy-", "sec-"];
+
+type HeaderMap = BTreeMap<String, String>;
+
+pub struct RequestHeaders(Arc<RwLock<HeaderMap>>);
+
+#[derive(Clone, Serialize)]
+pub struct RequestHeadersInfo {
+    headers: HeaderMap,
+    applied: bool,
+}
+
+pub fn init(app: &tauri::AppHandle) {
+    let headers: HeaderMap = settings::get(app, HEADERS_KEY).unwrap_or_default();
+    app.manage(RequestHeaders(Arc::new(RwLock::new(headers))));
+}
+
+/// Whether this platform's webview sends the configured headers at all.
+pub fn is_applied() -> bool {
+    cfg!(windows)
+}
+
+/// Origins that receive the headers: the backend, plus the dev server in development, which
+/// proxies `/api` to it.
+#[cfg(windows)]
+fn target_origins(app: &tauri::AppHandle) -> Vec<String> {
+    let mut origins = vec![API_ORIGIN.trim_end_matches('/').to_string()];
+    if tauri::is_dev() {
+        if let Some(dev_url) = &app.config().build.dev_url {
+            origins.push(dev_url.origin().ascii_serialization());
+        }
+    }
+    origins
+}
+
+fn validate_name(name: &str) -> Result<String, String> {
+    // RFC 9110 token characters.
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c));
+    if !valid {
+        return Err("invalid_header_name".to_string());
+    }
+    let lower = name.to_ascii_lowercase();
+    if FORBIDDEN.contains(&lower.as_str())
+        || FORBIDDEN_PREFIXES.iter().any(|prefix| lower.starts_with(prefix))
+    {
+        return Err("forbidden_header".to_string());
+    }
+    Ok(lower)
+}
+
+fn validate_value(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.len() > MAX_VALUE_LEN || value.chars().any(|c| c.is_control()) {
+        return Err("invalid_header_value".to_string());
+    }
+    Ok(value.to_string())
+}
+
+/// Hooks the configured headers into `webview`'s requests to the backend. Called for every
+/// webview when it is created; later changes apply to attached webviews immediately. Webviews
+/// created before [`init`] are skipped and attached by the startup code instead.
+pub fn attach(webview: &tauri::Webview) {
+    #[cfg(windows)]
+    {
+        let Some(headers) = webview.try_state::<RequestHeaders>().map(|state| state.0.clone()) else {
+            return;
+        };
+        let origins = target_origins(webview.app_handle());
+        let label = webview.label().to_string();
+        let result = webview.with_webview(move |platform_webview| {
+            if let Err(e) = unsafe { windows_attach(platform_webview, headers, origins) } {
+                log::warn!("Failed to attach request headers to {}: {}", label, e);
+            }
+        });
+        if let Err(e) = result {
+            log::warn!("Failed to attach request headers: {}", e);
+        }
+    }
+    #[cfg(not(windows))]
+    let _ = webview;
+}
+
+#[cfg(windows)]
+unsafe fn windows_attach(
+    webview: tauri::webview::PlatformWebview,
+    headers: Arc<RwLock<HeaderMap>>,
+    origins: Vec<String>,
+) -> windows::core::Result<()> {
+    use webview2_com::Microsoft::Web::WebView2::Win32::COREWEBVIEW2_WEB_RESOURCE_CONTEXT_ALL;
+    use webview2_com::WebResourceRequestedEventHandler;
+    use windows::core::{HSTRING, PWSTR};
+
+    let core = webview.controller().CoreWebView2()?;
+    for origin in &origins {
+        core.AddWebResourceRequestedFilter(
+            &HSTRING::from(format!("{}/*", origin)),
+            COREWEBVIEW2_WEB_RESOURCE_CONTEXT_ALL,
+        )?;
+    }
+    let handler = WebResourceRequestedEventHandler::create(Box::new(move |_, args| {
+        let Some(args) = args else { return Ok(()) };
+        let headers = headers.read().unwrap();
+        if headers.is_empty() {
+            return Ok(());
+        }
+        let request = args.Request()?;
+        let mut uri = PWSTR::null();
+        request.Uri(&mut uri)?;
+        let uri = webview2_com::take_pwstr(uri);
+        // The filters match by prefix; only exact origin matches get the headers.
+        let origin = url::Url::parse(&uri).map(|url| url.origin().ascii_serialization());
+        if !origin.is_ok_and(|origin| origins.contains(&origin)) {
+            return Ok(());
+        }
+        let request_headers = request.Headers()?;
+        for (name, value) in headers.iter() {
+            request_headers.SetHeader(&HSTRING::from(name.as_str()), &HSTRING::from(value.as_str()))?;
+        }
+        Ok(())
+    }));
+    let mut token = 0;
+    core.add_WebResourceRequested(&handler, &mut token)
+}
+
+fn info(app: &tauri::AppHandle) -> RequestHeadersInfo {
+    RequestHeadersInfo {
+        headers: app.state::<RequestHeaders>().0.read().unwrap().clone(),
+        applied: is_applied(),
+    }
+}
+
+/// Sets (or, with `None` or an empty value, removes) an extra request header.
+#[tauri::command]
+pub async fn set_request_header(
+    app: tauri::AppHandle,
+    name: String,
+    value: Option<String>,
+) -> Result<RequestHeadersInfo, String> {
+    let name = validate_name(name.trim())?;
+    let value = value.as_deref().map(validate_value).transpose()?;
+
+    let updated = {
+        let state = app.state::<RequestHeaders>();
+        let mut headers = state.0.write().unwrap();
+        match value {
+            Some(value) if !value.is_empty() => {
+                if !headers.contains_key(&name) && headers.len() >= MAX_HEADERS {
+                    return Err("too_many_headers".to_string());
+                }
+                headers.insert(name, value);
+            }
+            _ => {
+                headers.remove(&name);
+            }
+        }
+        headers.clone()
+    };
+    settings::set(&app, HEADERS_KEY, &updated)?;
+
+    let info = info(&app);
+    let _ = app.emit("request-headers-changed", &info);
+    Ok(info)
+}
+
+#[tauri::command]
+pub async fn get_request_headers(app: tauri::AppHandle) -> Result<RequestHeadersInfo, String> {
+    Ok(info(&app))
+}
@@ -0,0 +1,236 @@
+//! Retry queue for generation requests that failed with a transient server error (429/5xx).
+//! The queue lives in `<data dir>/retry-queue.json`, so pending retries survive a restart.
+//!
+//! The native side only schedules: when an entry is due, `retry-attempt` hands its payload to
+//! the frontend, which sends the request with its current session and answers with
+//! `report_retry_result` (auth tokens never end up in the file that way). Attempts go out one
+//! at a time, with exponential backoff per entry, and pause while "Nicht stören" is on (the
+//! only pause switch the app has so far).
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{Emitter, Manager};
+
+use crate::{dnd, paths};
+
+const QUEUE_FILE: &str = "retry-queue.json";
+const MAX_ATTEMPTS: u32 = 5;
+const MAX_ENTRIES: usize = 50;
+const MIN_BACKOFF_MS: u64 = 1_000;
+const MAX_BACKOFF_MS: u64 = 10 * 60 * 1000;
+/// An attempt the frontend never reported back on (e.g. it was reloaded) counts as failed.
+const ATTEMPT_TIMEOUT_MS: u64 = 2 * 60 * 1000;
+const TICK: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RetryEntry {
+    id: String,
+    payload: Value,
+    /// Attempts made so far, not counting the original request.
+    attempts: u32,
+    backoff_ms: u64,
+    next_attempt_at: u64,
+    created_at: u64,
+    /// When the running attempt was handed out; not persisted, a restart simply retries.
+    #[serde(skip)]
+    in_flight_since: Option<u64>,
+}
+
+#[derive(Default)]
+pub struct RetryQueue {
+    next_id: AtomicU64,
+    /// Loaded from disk on first use.
+    entries: Mutex<Option<Vec<RetryEntry>>>,
+}
+
+fn queue_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::data_dir(app)?.join(QUEUE_FILE))
+}
+
+fn load(app: &tauri::AppHandle) -> Vec<RetryEntry> {
+    queue_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(app: &tauri::AppHandle, entries: &[RetryEntry]) -> Result<(), String> {
+    let path = queue_path(app)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json).map_err(|e| e.to_string())?;
+    fs::rename(&tmp, &path).map_err(|e| e.to_string())
+}
+
+/// Runs `f` on the loaded queue, writing it back if `f` says it changed anything.
+fn update<T>(app: &tauri::AppHandle, f: impl FnOnce(&mut Vec<RetryEntry>) -> (T, bool)) -> Result<T, String> {
+    let queue = app.state::<RetryQueue>();
+    let mut entries = queue.entries.lock().unwrap();
+    let entries = entries.get_or_insert_with(|| load(app));
+    let (result, changed) = f(entries);
+    if changed {
+        save(app, entries)?;
+    }
+    Ok(result)
+}
+
+fn with_queue<T>(app: &tauri::AppHandle, f: impl FnOnce(&mut Vec<RetryEntry>) -> T) -> Result<T, String> {
+    update(app, |entries| (f(entries), true))
+}
+
+fn is_retryable(status: Option<u16>) -> bool {
+    // No status means the request never got an answer (network error, timeout).
+    status.is_none_or(|status| status == 429 || (500..=599).contains(&status))
+}
+
+/// Records a failed attempt and either schedules the next one or gives up.
+fn fail_attempt(app: &tauri::AppHandle, entries: &mut Vec<RetryEntry>, index: usize, status: Option<u16>) {
+    let now = crate::now_millis();
+    let entry = &mut entries[index];
+    entry.attempts += 1;
+    entry.in_flight_since = None;
+    if entry.attempts >= MAX_ATTEMPTS || !is_retryable(status) {
+        let entry = entries.remove(index);
+        log::warn!("Retry {} exhausted after {} attempts", entry.id, entry.attempts);
+        let _ = app.emit(
+            "retry-exhausted",
+            json!({ "id": entry.id, "attempts": entry.attempts, "status": status }),
+        );
+        return;
+    }
+    let delay = entry
+        .backoff_ms
+        .saturating_mul(1u64 << entry.attempts.min(16))
+        .min(MAX_BACKOFF_MS);
+    entry.next_attempt_at = now + delay;
+}
+
+fn tick(app: &tauri::AppHandle) -> Result<(), String> {
+    if dnd::is_enabled(app) {
+        return Ok(());
+    }
+    let now = crate::now_millis();
+    let attempt = update(app, |entries| {
+        let timed_out = entries.iter().position(|entry| {
+            entry
+                .in_flight_since
+                .is_some_and(|since| now.saturating_sub(since) > ATTEMPT_TIMEOUT_MS)
+        });
+        if let Some(index) = timed_out {
+            fail_attempt(app, entries, index, None);
+        }
+        let changed = timed_out.is_some();
+        if entries.iter().any(|entry| entry.in_flight_since.is_some()) {
+            return (None, changed);
+        }
+        match entries.iter_mut().find(|entry| entry.next_attempt_at <= now) {
+            Some(entry) => {
+                entry.in_flight_since = Some(now);
+                let attempt = json!({ "id": entry.id, "payload": entry.payload, "attempt": entry.attempts + 1 });
+                (Some(attempt), changed)
+            }
+            None => (None, changed),
+        }
+    })?;
+    if let Some(attempt) = attempt {
+        let _ = app.emit("retry-attempt", attempt);
+    }
+    Ok(())
+}
+
+/// Starts the worker. Entries left from the last session are picked up right away.
+pub fn init(app: &tauri::AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(TICK);
+        if let Err(e) = tick(&app) {
+            log::warn!("Retry queue: {}", e);
+        }
+    });
+}
+
+/// Queues `payload` for another try after `backoff_ms` (e.g. from `Retry-After`), doubling the
+/// wait after each failed attempt. Returns the entry id.
+#[tauri::command]
+pub async fn enqueue_retry(app: tauri::AppHandle, payload: Value, backoff_ms: u64) -> Result<String, String> {
+    let now = crate::now_millis();
+    let backoff_ms = backoff_ms.clamp(MIN_BACKOFF_MS, MAX_BACKOFF_MS);
+    let id = format!(
+        "retry-{}-{}",
+        now,
+        app.state::<RetryQueue>().next_id.fetch_add(1, Ordering::Relaxed) + 1
+    );
+    let entry = RetryEntry {
+        id: id.clone(),
+        payload,
+        attempts: 0,
+        backoff_ms,
+        next_attempt_at: now + backoff_ms,
+        created_at: now,
+        in_flight_since: None,
+    };
+    with_queue(&app, |entries| {
+        if entries.len() >= MAX_ENTRIES {
+            return Err("queue_full".to_string());
+        }
+        entries.push(entry);
+        Ok(())
+    })??;
+    Ok(id)
+}
+
+/// The frontend's answer to `retry-attempt`. `status` is the HTTP status, or `None` when the
+/// request failed without one; non-retryable statuses end the entry right away.
+#[tauri::command]
+pub async fn report_retry_result(
+    app: tauri::AppHandle,
+    id: String,
+    success: bool,
+    status: Option<u16>,
+) -> Result<(), String> {
+    let succeeded = with_queue(&app, |entries| -> Result<bool, String> {
+        let index = entries
+            .iter()
+            .position(|entry| entry.id == id)
+            .ok_or_else(|| "not_found".to_string())?;
+        if success {
+            entries.remove(index);
+            Ok(true)
+        } else {
+            fail_attempt(&app, entries, index, status);
+            Ok(false)
+        }
+    })??;
+    if succeeded {
+        let _ = app.emit("retry-succeeded", json!({ "id": id }));
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_retries(app: tauri::AppHandle) -> Result<Vec<RetryEntry>, String> {
+    update(&app, |entries| (entries.clone(), false))
+}
+
+#[tauri::command]
+pub async fn cancel_retry(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    with_queue(&app, |entries| {
+        let before = entries.len();
+        entries.retain(|entry| entry.id != id);
+        if entries.len() == before {
+            Err("not_found".to_string())
+        } else {
+            Ok(())
+        }
+    })?
+}
@@ -0,0 +1,75 @@
+//! Recovery boot for when the app won't start normally: `--safe-mode` skips the tray, the
+//! single-instance lock, restored window bounds and every menu customization, and logs at
+//! debug level. The check runs on the raw process args so it happens before any plugin loads.
+
+use std::path::PathBuf;
+
+use tauri::{Emitter, Manager};
+
+use crate::{paths, settings, startup};
+
+pub const SAFE_MODE_ARG: &str = "--safe-mode";
+
+pub struct SafeMode(pub bool);
+
+pub fn requested() -> bool {
+    std::env::args().any(|arg| arg == SAFE_MODE_ARG)
+}
+
+pub fn is_active(app: &tauri::AppHandle) -> bool {
+    app.try_state::<SafeMode>()
+        .map(|safe_mode| safe_mode.0)
+        .unwrap_or(false)
+}
+
+pub fn announce(app: &tauri::AppHandle) {
+    log::warn!("Running in safe mode");
+    startup::notify(app, "safe-mode-active", ());
+}
+
+fn window_state_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::config_dir(app)?.join(tauri_plugin_window_state::DEFAULT_FILENAME))
+}
+
+fn remove_if_exists(path: PathBuf) -> Result<(), String> {
+    match std::fs::remove_file(&path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Clears the native settings store and the saved window bounds. The frontend's `auth.json`
+/// is left alone, so resetting never logs anyone out.
+pub fn reset(app: &tauri::AppHandle) -> Result<(), String> {
+    match settings::store(app) {
+        Ok(store) => {
+            store.clear();
+            store.save().map_err(|e| e.to_string())?;
+        }
+        // A store that can't even be opened is exactly the case we're recovering from.
+        Err(_) => remove_if_exists(settings::path(app)?)?,
+    }
+    remove_if_exists(window_state_path(app)?)?;
+
+    log::info!("Settings reset");
+    let _ = app.emit("settings-reset", ());
+    Ok(())
+}
+
+/// Relaunches into safe mode. The new process skips the single-instance lock, so it isn't
+/// handed back to this one while it is still shutting down.
+#[tauri::command]
+pub async fn start_safe_mode(app: tauri::AppHandle) -> Result<(), String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    std::process::Command::new(exe)
+        .arg(SAFE_MODE_ARG)
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    app.exit(0);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn reset_settings(app: tauri::AppHandle) -> Result<(), String> {
+    reset(&app)
+}
@@ -0,0 +1,132 @@
+//! Typed access to the native settings store.
+//! Kept separate from the frontend's `auth.json` so resetting preferences never logs anyone out.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use tauri_plugin_store::{Store, StoreExt};
+
+use crate::{paths, plugins, startup};
+
+pub const STORE_FILE: &str = "settings.json";
+
+/// Where the store plugin keeps [`STORE_FILE`] on disk.
+pub fn path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(paths::data_dir(app)?.join(STORE_FILE))
+}
+
+/// Opens the settings store; fails with `plugin_unavailable: store` when the store plugin
+/// didn't come up, in which case every setting reads as its default.
+pub fn store(app: &tauri::AppHandle) -> Result<Arc<Store<tauri::Wry>>, String> {
+    plugins::require(app, plugins::STORE)?;
+    app.store(path(app)?).map_err(|e| e.to_string())
+}
+
+pub fn get<T: DeserializeOwned>(app: &tauri::AppHandle, key: &str) -> Option<T> {
+    let store = store(app).ok()?;
+    store
+        .get(key)
+        .and_then(|value| serde_json::from_value(value).ok())
+}
+
+pub fn set<T: Serialize>(app: &tauri::AppHandle, key: &str, value: T) -> Result<(), String> {
+    let store = store(app)?;
+    let value = serde_json::to_value(value).map_err(|e| e.to_string())?;
+    store.set(key, value);
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Removes `key`, so it reads as its default again.
+pub fn remove(app: &tauri::AppHandle, key: &str) -> Result<(), String> {
+    let store = store(app)?;
+    store.delete(key);
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Checks the store file before anything opens it. A file that doesn't parse (typically a
+/// write cut short by a crash) is copied aside first, then replaced by the complete top-level
+/// entries that can still be read, or removed so the defaults apply. Emits `store-recovered`.
+pub fn recover_if_corrupt(app: &tauri::AppHandle) {
+    let Ok(path) = path(app) else { return };
+    let Ok(content) = std::fs::read_to_string(&path) else { return };
+    if serde_json::from_str::<Map<String, Value>>(&content).is_ok() {
+        return;
+    }
+
+    let backup = path.with_file_name(format!("{}.corrupt-{}", STORE_FILE, crate::now_millis()));
+    if let Err(e) = std::fs::copy(&path, &backup) {
+        // Without a backup we'd be destroying the only copy; let the store plugin deal with it.
+        log::error!("Corrupt settings store, backup to {} failed: {}", backup.display(), e);
+        return;
+    }
+
+    let repaired = repair(&content);
+    let result = match &repaired {
+        Some(entries) => serde_json::to_string_pretty(entries)
+            .map_err(|e| e.to_string())
+            .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string())),
+        None => std::fs::remove_file(&path).map_err(|e| e.to_string()),
+    };
+    if let Err(e) = result {
+        log::error!("Failed to recover settings store: {}", e);
+        return;
+    }
+
+    let recovered_keys = repaired.as_ref().map(|entries| entries.len()).unwrap_or(0);
+    log::warn!(
+        "Recovered corrupt settings store ({} keys kept, backup at {})",
+        recovered_keys,
+        backup.display()
+    );
+    startup::notify(
+        app,
+        "store-recovered",
+        json!({
+            "backup": backup.to_string_lossy(),
+            "repaired": repaired.is_some(),
+            "recoveredKeys": recovered_keys,
+        }),
+    );
+}
+
+/// Truncates `content` to the last point where the top-level object was complete (after a
+/// closing brace or between two entries) and parses that.
+fn repair(content: &str) -> Option<Map<String, Value>> {
+    let mut candidates = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in content.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' | '[' => depth += 1,
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    candidates.push(format!("{}}}", &content[..i]));
+                }
+            }
+            ',' if depth == 1 => candidates.push(format!("{}}}", &content[..i])),
+            _ => {}
+        }
+    }
+
+    candidates
+        .iter()
+        .rev()
+        .find_map(|candidate| serde_json::from_str::<Map<String, Value>>(candidate).ok())
+        .filter(|entries| !entries.is_empty())
+}
@@ -0,0 +1,138 @@
+//! Time-limited links to a document, e.g. to continue on another of one's own devices:
+//! `gruenerator://open?doc=<id>&token=<token>`. The token carries its expiry and a keyed
+//! BLAKE3 MAC over document id and expiry. The key is generated once per user and kept in the
+//! OS keychain, so only installs that share that keychain entry (the same machine, or a
+//! synced keychain) accept the link.
+
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::Manager;
+
+use crate::{deep_link, documents};
+
+const KEYCHAIN_SERVICE: &str = "de.gruenerator.desktop";
+const KEYCHAIN_USER: &str = "export-link-signing-key";
+const TOKEN_VERSION: &str = "v1";
+
+/// Signing key, loaded from the keychain on first use.
+#[derive(Default)]
+pub struct SigningKey(Mutex<Option<[u8; 32]>>);
+
+#[derive(Clone, Serialize)]
+pub struct ExportLink {
+    url: String,
+    token: String,
+    expires_at: String,
+}
+
+fn decode_key(hex: &str) -> Option<[u8; 32]> {
+    let mut key = [0u8; 32];
+    if hex.len() != 64 {
+        return None;
+    }
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    Some(key)
+}
+
+fn load_or_create_key() -> Result<[u8; 32], String> {
+    let entry = keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_USER).map_err(|e| e.to_string())?;
+    match entry.get_password() {
+        Ok(stored) => {
+            if let Some(key) = decode_key(&stored) {
+                return Ok(key);
+            }
+            log::warn!("Stored export link key is malformed, generating a new one");
+        }
+        Err(keyring::Error::NoEntry) => {}
+        Err(e) => return Err(format!("keychain_unavailable: {}", e)),
+    }
+
+    let mut key = [0u8; 32];
+    getrandom::getrandom(&mut key).map_err(|e| e.to_string())?;
+    let hex: String = key.iter().map(|byte| format!("{:02x}", byte)).collect();
+    entry
+        .set_password(&hex)
+        .map_err(|e| format!("keychain_unavailable: {}", e))?;
+    Ok(key)
+}
+
+fn key<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Result<[u8; 32], String> {
+    let state = app.state::<SigningKey>();
+    let mut cached = state.0.lock().unwrap();
+    if let Some(key) = *cached {
+        return Ok(key);
+    }
+    let key = load_or_create_key()?;
+    *cached = Some(key);
+    Ok(key)
+}
+
+fn mac(key: &[u8; 32], doc_id: &str, expires_ms: i64) -> String {
+    let message = format!("{}\n{}\n{}", TOKEN_VERSION, doc_id, expires_ms);
+    blake3::keyed_hash(key, message.as_bytes()).to_hex().to_string()
+}
+
+/// Checks `token` for `doc_id`. Errors are `token_malformed`, `token_invalid` (wrong
+/// signature, i.e. tampered or from another key) and `token_expired`, in that order.
+pub fn validate<R: tauri::Runtime>(app: &tauri::AppHandle<R>, doc_id: &str, token: &str) -> Result<(), String> {
+    let mut parts = token.split('.');
+    let (Some(TOKEN_VERSION), Some(expires), Some(signature), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err("token_malformed".to_string());
+    };
+    let expires_ms: i64 = expires.parse().map_err(|_| "token_malformed".to_string())?;
+
+    let expected = mac(&key(app)?, doc_id, expires_ms);
+    // Compare the full strings without short-circuiting on the first differing byte.
+    let matches = expected.len() == signature.len()
+        && expected
+            .bytes()
+            .zip(signature.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0;
+    if !matches {
+        return Err("token_invalid".to_string());
+    }
+    if chrono::Utc::now().timestamp_millis() > expires_ms {
+        return Err("token_expired".to_string());
+    }
+    Ok(())
+}
+
+/// `expires_at` is an RFC 3339 timestamp in the future.
+#[tauri::command]
+pub async fn create_export_token(
+    app: tauri::AppHandle,
+    doc_id: String,
+    expires_at: String,
+) -> Result<ExportLink, String> {
+    documents::validate_id(&doc_id)?;
+    let expires = chrono::DateTime::parse_from_rfc3339(&expires_at)
+        .map_err(|e| format!("invalid expiry: {}", e))?;
+    let expires_ms = expires.timestamp_millis();
+    if expires_ms <= chrono::Utc::now().timestamp_millis() {
+        return Err("expiry_in_past".to_string());
+    }
+
+    let token = format!("{}.{}.{}", TOKEN_VERSION, expires_ms, mac(&key(&app)?, &doc_id, expires_ms));
+    let mut url = url::Url::parse(&format!("{}://open", deep_link::active_scheme(&app)))
+        .map_err(|e| e.to_string())?;
+    url.query_pairs_mut()
+        .append_pair("doc", &doc_id)
+        .append_pair("token", &token);
+
+    Ok(ExportLink {
+        url: url.to_string(),
+        token,
+        expires_at: expires.with_timezone(&chrono::Utc).to_rfc3339(),
+    })
+}
+
+#[tauri::command]
+pub async fn validate_export_token(app: tauri::AppHandle, doc_id: String, token: String) -> Result<(), String> {
+    validate(&app, &doc_id, &token)
+}
@@ -0,0 +1,89 @@
+//! Native helper launches the webview may request. Only ids listed here can run, each with a
+//! fixed program and an argument check, instead of exposing arbitrary shell execution.
+
+use std::path::Path;
+use std::process::Command;
+
+struct AllowedCommand {
+    id: &'static str,
+    program: &'static str,
+    /// Validates the caller's arguments and maps them to the final argv, or rejects them.
+    args: fn(&[String]) -> Option<Vec<String>>,
+}
+
+fn existing_path(args: &[String]) -> Option<&Path> {
+    match args {
+        [path] if Path::new(path).is_absolute() && Path::new(path).exists() => Some(Path::new(path)),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "windows")]
+const ALLOWED_COMMANDS: &[AllowedCommand] = &[
+    AllowedCommand {
+        id: "open_notification_settings",
+        program: "explorer.exe",
+        args: |args| args.is_empty().then(|| vec!["ms-settings:notifications".to_string()]),
+    },
+    AllowedCommand {
+        id: "reveal_in_file_manager",
+        program: "explorer.exe",
+        args: |args| existing_path(args).map(|path| vec![format!("/select,{}", path.display())]),
+    },
+];
+
+#[cfg(target_os = "macos")]
+const ALLOWED_COMMANDS: &[AllowedCommand] = &[
+    AllowedCommand {
+        id: "open_notification_settings",
+        program: "open",
+        args: |args| {
+            args.is_empty().then(|| {
+                vec!["x-apple.systempreferences:com.apple.preference.notifications".to_string()]
+            })
+        },
+    },
+    AllowedCommand {
+        id: "reveal_in_file_manager",
+        program: "open",
+        args: |args| existing_path(args).map(|path| vec!["-R".to_string(), path.display().to_string()]),
+    },
+];
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const ALLOWED_COMMANDS: &[AllowedCommand] = &[
+    AllowedCommand {
+        id: "open_notification_settings",
+        program: "gnome-control-center",
+        args: |args| args.is_empty().then(|| vec!["notifications".to_string()]),
+    },
+    AllowedCommand {
+        id: "reveal_in_file_manager",
+        program: "xdg-open",
+        // xdg-open cannot select a file, so open the containing folder instead.
+        args: |args| {
+            existing_path(args)
+                .and_then(Path::parent)
+                .map(|folder| vec![folder.display().to_string()])
+        },
+    },
+];
+
+#[tauri::command]
+pub async fn run_allowed_command(id: String, args: Vec<String>) -> Result<(), String> {
+    let Some(command) = ALLOWED_COMMANDS.iter().find(|command| command.id == id) else {
+        log::warn!("Rejected helper command: {}", id);
+        return Err("not_allowed".to_string());
+    };
+    let Some(argv) = (command.args)(&args) else {
+        log::warn!("Rejected arguments for helper command {}: {:?}", id, args);
+        return Err("not_allowed".to_string());
+    };
+
+    log::info!("Running helper command {}: {} {:?}", id, command.program, argv);
+    Command::new(command.program)
+        .args(&argv)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,211 @@
+//! Single source of truth for keyboard shortcuts. The native menu reads its accelerators from
+//! here, so the help overlay data can't drift from what is actually bound.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::{clipboard, menu, quick_capture, safe_mode, settings};
+
+const OVERRIDES_KEY: &str = "shortcut_overrides";
+const GLOBAL: &str = "Global";
+
+struct ShortcutDefinition {
+    id: &'static str,
+    category: &'static str,
+    description: &'static str,
+    /// Empty for shortcuts that stay unbound until the user assigns one.
+    accelerator: &'static str,
+    /// Native items (undo, copy, …) are bound by the OS and can't be reassigned.
+    customizable: bool,
+}
+
+const SHORTCUTS: &[ShortcutDefinition] = &[
+    ShortcutDefinition { id: "new", category: "Datei", description: "Neuer Text", accelerator: "CmdOrCtrl+N", customizable: true },
+    ShortcutDefinition { id: "print", category: "Datei", description: "Drucken", accelerator: "CmdOrCtrl+P", customizable: true },
+    ShortcutDefinition { id: "settings", category: "Datei", description: "Einstellungen öffnen", accelerator: "CmdOrCtrl+,", customizable: true },
+    ShortcutDefinition { id: "undo", category: "Bearbeiten", description: "Rückgängig", accelerator: "CmdOrCtrl+Z", customizable: false },
+    ShortcutDefinition { id: "redo", category: "Bearbeiten", description: "Wiederholen", accelerator: "CmdOrCtrl+Shift+Z", customizable: false },
+    ShortcutDefinition { id: "cut", category: "Bearbeiten", description: "Ausschneiden", accelerator: "CmdOrCtrl+X", customizable: false },
+    ShortcutDefinition { id: "copy", category: "Bearbeiten", description: "Kopieren", accelerator: "CmdOrCtrl+C", customizable: false },
+    ShortcutDefinition { id: "paste", category: "Bearbeiten", description: "Einfügen", accelerator: "CmdOrCtrl+V", customizable: false },
+    ShortcutDefinition { id: "select_all", category: "Bearbeiten", description: "Alles auswählen", accelerator: "CmdOrCtrl+A", customizable: false },
+    ShortcutDefinition { id: "reload", category: "Ansicht", description: "Neu laden", accelerator: "CmdOrCtrl+R", customizable: true },
+    ShortcutDefinition { id: "fullscreen", category: "Ansicht", description: "Vollbild umschalten", accelerator: "F11", customizable: true },
+    ShortcutDefinition { id: "zoom_in", category: "Ansicht", description: "Vergrößern", accelerator: "CmdOrCtrl+=", customizable: true },
+    ShortcutDefinition { id: "zoom_out", category: "Ansicht", description: "Verkleinern", accelerator: "CmdOrCtrl+Minus", customizable: true },
+    ShortcutDefinition { id: "zoom_reset", category: "Ansicht", description: "Originalgröße", accelerator: "CmdOrCtrl+0", customizable: true },
+    ShortcutDefinition { id: "next_window", category: "Ansicht", description: "Nächstes Fenster", accelerator: "Ctrl+Tab", customizable: true },
+    ShortcutDefinition { id: "previous_window", category: "Ansicht", description: "Vorheriges Fenster", accelerator: "Ctrl+Shift+Tab", customizable: true },
+    ShortcutDefinition { id: quick_capture::SHORTCUT_ID, category: GLOBAL, description: "Schnellerfassung öffnen", accelerator: "CmdOrCtrl+Shift+Space", customizable: true },
+    ShortcutDefinition { id: clipboard::GENERATE_SHORTCUT_ID, category: GLOBAL, description: "Aus Zwischenablage generieren", accelerator: "", customizable: true },
+];
+
+const CATEGORIES: &[&str] = &["Datei", "Bearbeiten", "Ansicht", GLOBAL];
+
+/// Accelerators of the `Global` shortcuts currently registered with the OS, by id, so a
+/// changed binding can replace the old one.
+#[derive(Default)]
+pub struct GlobalShortcuts(Mutex<HashMap<String, String>>);
+
+#[derive(Clone, Serialize)]
+pub struct ShortcutEntry {
+    id: String,
+    description: String,
+    accelerator: String,
+    customized: bool,
+}
+
+#[derive(Clone, Serialize)]
+pub struct ShortcutGroup {
+    category: String,
+    shortcuts: Vec<ShortcutEntry>,
+}
+
+fn overrides(app: &tauri::AppHandle) -> HashMap<String, String> {
+    if safe_mode::is_active(app) {
+        return HashMap::new();
+    }
+    settings::get(app, OVERRIDES_KEY).unwrap_or_default()
+}
+
+/// The accelerator currently bound to `id`, taking user overrides into account.
+pub fn accelerator(app: &tauri::AppHandle, id: &str) -> Option<String> {
+    let definition = SHORTCUTS.iter().find(|shortcut| shortcut.id == id)?;
+    if definition.customizable {
+        if let Some(custom) = overrides(app).remove(id) {
+            return Some(custom);
+        }
+    }
+    Some(definition.accelerator)
+        .filter(|accelerator| !accelerator.is_empty())
+        .map(str::to_string)
+}
+
+fn run_global(app: &tauri::AppHandle, id: &str) {
+    let result = match id {
+        quick_capture::SHORTCUT_ID => quick_capture::open(app),
+        clipboard::GENERATE_SHORTCUT_ID => clipboard::quick_generate(app),
+        _ => Ok(()),
+    };
+    if let Err(e) = result {
+        log::error!("Global shortcut {} failed: {}", id, e);
+    }
+}
+
+/// (Re-)registers the system-wide hotkey for `id` from the current bindings.
+fn register_global(app: &tauri::AppHandle, id: &'static str) -> Result<(), String> {
+    let state = app.state::<GlobalShortcuts>();
+    let mut registered = state.0.lock().unwrap();
+    if let Some(previous) = registered.remove(id) {
+        let _ = app.global_shortcut().unregister(previous.as_str());
+    }
+    let Some(accelerator) = accelerator(app, id) else {
+        return Ok(());
+    };
+    app.global_shortcut()
+        .on_shortcut(accelerator.as_str(), move |app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                run_global(app, id);
+            }
+        })
+        .map_err(|e| e.to_string())?;
+    registered.insert(id.to_string(), accelerator);
+    Ok(())
+}
+
+/// Registers all `Global` shortcuts. Skipped in safe mode, where nothing hooks into the OS.
+pub fn register_all_global(app: &tauri::AppHandle) {
+    if safe_mode::is_active(app) {
+        return;
+    }
+    for definition in SHORTCUTS.iter().filter(|shortcut| shortcut.category == GLOBAL) {
+        if let Err(e) = register_global(app, definition.id) {
+            log::warn!("Failed to register global shortcut {}: {}", definition.id, e);
+        }
+    }
+}
+
+pub fn shortcut_map(app: &tauri::AppHandle) -> Vec<ShortcutGroup> {
+    let overrides = overrides(app);
+    CATEGORIES
+        .iter()
+        .map(|category| ShortcutGroup {
+            category: category.to_string(),
+            shortcuts: SHORTCUTS
+                .iter()
+                .filter(|shortcut| shortcut.category == *category)
+                .map(|shortcut| {
+                    let custom = overrides.get(shortcut.id).filter(|_| shortcut.customizable);
+                    ShortcutEntry {
+                        id: shortcut.id.to_string(),
+                        description: shortcut.description.to_string(),
+                        accelerator: custom
+                            .cloned()
+                            .unwrap_or_else(|| shortcut.accelerator.to_string()),
+                        customized: custom.is_some(),
+                    }
+                })
+                .collect(),
+        })
+        .filter(|group| !group.shortcuts.is_empty())
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_shortcut_map(app: tauri::AppHandle) -> Result<Vec<ShortcutGroup>, String> {
+    Ok(shortcut_map(&app))
+}
+
+/// Reassigns (or, with `None`, resets) a menu shortcut and rebuilds the menu. Accelerators that
+/// don't parse are rejected before anything is saved, since the menu would silently drop
+/// them; the previous binding is restored if registering a global shortcut fails.
+#[tauri::command]
+pub async fn set_shortcut_override(
+    app: tauri::AppHandle,
+    id: String,
+    accelerator: Option<String>,
+) -> Result<(), String> {
+    let definition = SHORTCUTS
+        .iter()
+        .find(|shortcut| shortcut.id == id)
+        .ok_or_else(|| format!("unknown shortcut: {}", id))?;
+    if !definition.customizable {
+        return Err(format!("shortcut {} cannot be changed", id));
+    }
+
+    let previous = overrides(&app);
+    let mut updated = previous.clone();
+    match accelerator {
+        Some(accelerator) if !accelerator.trim().is_empty() => {
+            let accelerator = accelerator.trim();
+            accelerator
+                .parse::<Shortcut>()
+                .map_err(|e| format!("invalid accelerator: {}", e))?;
+            updated.insert(id.clone(), accelerator.to_string());
+        }
+        _ => {
+            updated.remove(&id);
+        }
+    }
+    settings::set(&app, OVERRIDES_KEY, &updated)?;
+
+    if let Err(e) = menu::rebuild(&app) {
+        settings::set(&app, OVERRIDES_KEY, &previous)?;
+        let _ = menu::rebuild(&app);
+        return Err(format!("invalid accelerator: {}", e));
+    }
+    if definition.category == GLOBAL && !safe_mode::is_active(&app) {
+        if let Err(e) = register_global(&app, definition.id) {
+            settings::set(&app, OVERRIDES_KEY, &previous)?;
+            let _ = register_global(&app, definition.id);
+            return Err(format!("invalid accelerator: {}", e));
+        }
+    }
+
+    let _ = app.emit("shortcuts-changed", shortcut_map(&app));
+    Ok(())
+}
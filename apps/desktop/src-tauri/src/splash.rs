@@ -0,0 +1,73 @@
+//! The splash screen shown while the main window loads. It isn't created from the config
+//! (`create: false`) so the `disable_splash` setting can skip it; the main window is then
+//! shown as soon as its page has loaded. The splash still comes back to host a load error.
+
+use tauri::Manager;
+
+use crate::{autostart, settings, startup};
+
+const DISABLE_KEY: &str = "disable_splash";
+pub const WINDOW_LABEL: &str = "splashscreen";
+
+/// Whether this launch skipped the splash; changing the setting only affects the next one.
+struct SplashDisabled(bool);
+
+pub fn is_disabled(app: &tauri::AppHandle) -> bool {
+    app.try_state::<SplashDisabled>()
+        .map(|disabled| disabled.0)
+        .unwrap_or(false)
+}
+
+/// Creates the splash window from its config entry.
+pub fn open(app: &tauri::AppHandle) -> Option<tauri::WebviewWindow> {
+    if let Some(splashscreen) = app.get_webview_window(WINDOW_LABEL) {
+        return Some(splashscreen);
+    }
+    let config = app
+        .config()
+        .app
+        .windows
+        .iter()
+        .find(|window| window.label == WINDOW_LABEL)?;
+    tauri::WebviewWindowBuilder::from_config(app, config)
+        .and_then(|builder| builder.build())
+        .map_err(|e| log::error!("Failed to create splash screen: {}", e))
+        .ok()
+}
+
+/// Opens the splash for this launch unless the user turned it off.
+pub fn init(app: &tauri::AppHandle) {
+    let disabled = settings::get(app, DISABLE_KEY).unwrap_or(false);
+    app.manage(SplashDisabled(disabled));
+    if disabled {
+        log::info!("Splash screen disabled");
+    } else {
+        open(app);
+    }
+}
+
+/// Closes the splash, if there is one, and reveals the main window, unless this launch
+/// started minimized to the tray. `MAIN_SHOWN` is marked either way: the window is ready.
+pub fn finish(app: &tauri::AppHandle) {
+    if let Some(splashscreen) = app.get_webview_window(WINDOW_LABEL) {
+        let _ = splashscreen.close();
+        startup::mark(app, startup::SPLASH_CLOSED);
+    }
+    if let Some(main_window) = app.get_webview_window("main") {
+        if !autostart::started_minimized(app) {
+            let _ = main_window.show();
+        }
+        startup::mark(app, startup::MAIN_SHOWN);
+    }
+}
+
+/// Takes effect on the next launch.
+#[tauri::command]
+pub async fn set_disable_splash(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    settings::set(&app, DISABLE_KEY, enabled)
+}
+
+#[tauri::command]
+pub async fn get_disable_splash(app: tauri::AppHandle) -> Result<bool, String> {
+    Ok(settings::get(&app, DISABLE_KEY).unwrap_or(false))
+}
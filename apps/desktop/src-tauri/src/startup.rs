@@ -0,0 +1,129 @@
+//! Events raised during `setup` fire before the frontend has registered its listeners.
+//! They are queued here and flushed once the main window is revealed.
+//!
+//! Also keeps the startup trace: one `Instant` per phase, so startup time can be measured and
+//! compared between builds with `get_startup_trace`.
+
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{Emitter, Manager};
+
+pub struct StartupNotices(Mutex<Option<Vec<(String, Value)>>>);
+
+impl Default for StartupNotices {
+    fn default() -> Self {
+        Self(Mutex::new(Some(Vec::new())))
+    }
+}
+
+pub fn notify<S: Serialize>(app: &tauri::AppHandle, event: &str, payload: S) {
+    let payload = serde_json::to_value(payload).unwrap_or(Value::Null);
+    let notices = app.state::<StartupNotices>();
+    let mut queue = notices.0.lock().unwrap();
+    match queue.as_mut() {
+        Some(pending) => pending.push((event.to_string(), payload)),
+        None => {
+            let _ = app.emit(event, payload);
+        }
+    }
+}
+
+pub fn flush(app: &tauri::AppHandle) {
+    let pending = app.state::<StartupNotices>().0.lock().unwrap().take();
+    for (event, payload) in pending.unwrap_or_default() {
+        let _ = app.emit(&event, payload);
+    }
+}
+
+/// Startup phases in the order they normally complete.
+pub const PLUGINS_INITIALIZED: &str = "plugins_initialized";
+pub const MENU_BUILT: &str = "menu_built";
+pub const TRAY_BUILT: &str = "tray_built";
+pub const DEEP_LINK_REGISTERED: &str = "deep_link_registered";
+pub const SPLASH_CLOSED: &str = "splash_closed";
+pub const MAIN_SHOWN: &str = "main_shown";
+const PHASES: &[&str] = &[
+    PLUGINS_INITIALIZED,
+    MENU_BUILT,
+    TRAY_BUILT,
+    DEEP_LINK_REGISTERED,
+    SPLASH_CLOSED,
+    MAIN_SHOWN,
+];
+
+/// Created first thing in `run`, which is as close to process start as the app gets.
+pub struct StartupTrace {
+    started: Instant,
+    marks: Mutex<Vec<(&'static str, Instant)>>,
+}
+
+impl Default for StartupTrace {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            marks: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
+pub struct StartupPhase {
+    phase: &'static str,
+    /// Milliseconds since process start; `None` if the phase didn't happen (yet), e.g. the
+    /// tray in safe mode.
+    at_ms: Option<f64>,
+}
+
+#[derive(Clone, Serialize)]
+pub struct StartupTraceReport {
+    phases: Vec<StartupPhase>,
+    /// Until the main window was shown, or so far if it hasn't been.
+    total_ms: f64,
+    complete: bool,
+}
+
+/// Records that `phase` is done. Only the first time counts, so reopening the main window
+/// later doesn't move the mark.
+pub fn mark(app: &tauri::AppHandle, phase: &'static str) {
+    let Some(trace) = app.try_state::<StartupTrace>() else { return };
+    let mut marks = trace.marks.lock().unwrap();
+    if !marks.iter().any(|(recorded, _)| *recorded == phase) {
+        marks.push((phase, Instant::now()));
+    }
+}
+
+pub fn is_marked(app: &tauri::AppHandle, phase: &str) -> bool {
+    app.try_state::<StartupTrace>()
+        .is_some_and(|trace| trace.marks.lock().unwrap().iter().any(|(recorded, _)| *recorded == phase))
+}
+
+fn millis(duration: std::time::Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[tauri::command]
+pub async fn get_startup_trace(trace: tauri::State<'_, StartupTrace>) -> Result<StartupTraceReport, String> {
+    let marks = trace.marks.lock().unwrap();
+    let at = |phase: &str| {
+        marks
+            .iter()
+            .find(|(recorded, _)| *recorded == phase)
+            .map(|(_, instant)| *instant)
+    };
+
+    let shown = at(MAIN_SHOWN);
+    Ok(StartupTraceReport {
+        phases: PHASES
+            .iter()
+            .map(|phase| StartupPhase {
+                phase,
+                at_ms: at(phase).map(|instant| millis(instant - trace.started)),
+            })
+            .collect(),
+        total_ms: millis(shown.unwrap_or_else(Instant::now) - trace.started),
+        complete: shown.is_some(),
+    })
+}
@@ -0,0 +1,109 @@
+//! OS accessibility preferences the UI adapts to. They aren't reported through window events,
+//! so each is polled and its `…-changed` event goes out when the value flips. Where a platform
+//! has no such setting the preference reads as `false`.
+
+use std::time::Duration;
+
+use tauri::Emitter;
+
+use crate::startup;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// "Reduce motion" on macOS; on Windows, "Show animations in Windows" turned off.
+fn reduced_motion() -> bool {
+    #[cfg(windows)]
+    unsafe {
+        use windows::core::BOOL;
+        use windows::Win32::UI::WindowsAndMessaging::{
+            SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+        };
+
+        let mut animations = BOOL(1);
+        let read = SystemParametersInfoW(
+            SPI_GETCLIENTAREAANIMATION,
+            0,
+            Some(&mut animations as *mut BOOL as *mut _),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        );
+        read.is_ok() && !animations.as_bool()
+    }
+    #[cfg(target_os = "macos")]
+    {
+        objc2_app_kit::NSWorkspace::sharedWorkspace().accessibilityDisplayShouldReduceMotion()
+    }
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        false
+    }
+}
+
+/// "High contrast" on Windows, "Increase contrast" on macOS.
+fn high_contrast() -> bool {
+    #[cfg(windows)]
+    unsafe {
+        use windows::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW};
+        use windows::Win32::UI::WindowsAndMessaging::{
+            SystemParametersInfoW, SPI_GETHIGHCONTRAST, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+        };
+
+        let mut contrast = HIGHCONTRASTW {
+            cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+            ..Default::default()
+        };
+        let read = SystemParametersInfoW(
+            SPI_GETHIGHCONTRAST,
+            contrast.cbSize,
+            Some(&mut contrast as *mut HIGHCONTRASTW as *mut _),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        );
+        read.is_ok() && contrast.dwFlags.contains(HCF_HIGHCONTRASTON)
+    }
+    #[cfg(target_os = "macos")]
+    {
+        objc2_app_kit::NSWorkspace::sharedWorkspace().accessibilityDisplayShouldIncreaseContrast()
+    }
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        false
+    }
+}
+
+/// Emits `event` with the new value whenever `read` changes.
+fn watch(app: &tauri::AppHandle, event: &'static str, read: fn() -> bool) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut last = read();
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let current = read();
+            if current != last {
+                let _ = app.emit(event, current);
+                last = current;
+            }
+        }
+    });
+}
+
+/// Starts the watchers. An active high-contrast mode is announced right away, so the frontend
+/// can load its stylesheet while it starts up.
+pub fn init(app: &tauri::AppHandle) {
+    if cfg!(not(any(windows, target_os = "macos"))) {
+        return;
+    }
+    if high_contrast() {
+        startup::notify(app, "high-contrast-changed", true);
+    }
+    watch(app, "reduced-motion-changed", reduced_motion);
+    watch(app, "high-contrast-changed", high_contrast);
+}
+
+#[tauri::command]
+pub async fn get_reduced_motion() -> Result<bool, String> {
+    Ok(reduced_motion())
+}
+
+#[tauri::command]
+pub async fn get_high_contrast() -> Result<bool, String> {
+    Ok(high_contrast())
+}
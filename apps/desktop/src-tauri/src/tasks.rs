@@ -0,0 +1,179 @@
+//! Registry of long-running background work (exports, downloads, backups, pending
+//! notifications). Work registers itself via [`TaskRegistry::start`] and keeps the returned
+//! [`TaskHandle`] alive while it runs; dropping the handle removes the task again.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+
+use crate::app_state;
+
+#[derive(Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskKind {
+    Export,
+    Download,
+    Backup,
+    Notification,
+}
+
+#[derive(Clone, Serialize)]
+pub struct TaskInfo {
+    pub id: String,
+    pub kind: TaskKind,
+    pub label: String,
+    pub progress: Option<f64>,
+    pub started_at: u64,
+    pub cancellable: bool,
+}
+
+type CancelHook = Box<dyn FnOnce() + Send>;
+
+struct TaskEntry {
+    info: TaskInfo,
+    cancelled: Arc<AtomicBool>,
+    on_cancel: Option<CancelHook>,
+}
+
+#[derive(Default)]
+pub struct TaskRegistry {
+    next_id: AtomicU64,
+    tasks: Mutex<HashMap<String, TaskEntry>>,
+}
+
+impl TaskRegistry {
+    pub fn start(
+        &self,
+        app: &tauri::AppHandle,
+        kind: TaskKind,
+        label: impl Into<String>,
+        cancellable: bool,
+    ) -> TaskHandle {
+        let id = format!("task-{}", self.next_id.fetch_add(1, Ordering::Relaxed) + 1);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let info = TaskInfo {
+            id: id.clone(),
+            kind,
+            label: label.into(),
+            progress: None,
+            started_at: crate::now_millis(),
+            cancellable,
+        };
+        self.tasks.lock().unwrap().insert(
+            id.clone(),
+            TaskEntry {
+                info,
+                cancelled: cancelled.clone(),
+                on_cancel: None,
+            },
+        );
+        self.notify(app);
+
+        TaskHandle {
+            app: app.clone(),
+            id,
+            cancelled,
+        }
+    }
+
+    pub fn list(&self) -> Vec<TaskInfo> {
+        let mut tasks: Vec<TaskInfo> = self
+            .tasks
+            .lock()
+            .unwrap()
+            .values()
+            .map(|entry| entry.info.clone())
+            .collect();
+        tasks.sort_by_key(|task| task.started_at);
+        tasks
+    }
+
+    fn cancel(&self, id: &str) -> Result<(), String> {
+        let hook = {
+            let mut tasks = self.tasks.lock().unwrap();
+            let entry = tasks.get_mut(id).ok_or_else(|| format!("unknown task: {}", id))?;
+            if !entry.info.cancellable {
+                return Err(format!("task {} cannot be cancelled", id));
+            }
+            entry.cancelled.store(true, Ordering::SeqCst);
+            entry.on_cancel.take()
+        };
+        // Run outside the lock: hooks typically drop the task's handle, which unregisters it.
+        if let Some(hook) = hook {
+            hook();
+        }
+        Ok(())
+    }
+
+    fn set_cancel_hook(&self, id: &str, hook: CancelHook) {
+        if let Some(entry) = self.tasks.lock().unwrap().get_mut(id) {
+            entry.on_cancel = Some(hook);
+        }
+    }
+
+    fn set_progress(&self, app: &tauri::AppHandle, id: &str, progress: f64) {
+        if let Some(entry) = self.tasks.lock().unwrap().get_mut(id) {
+            entry.info.progress = Some(progress.clamp(0.0, 1.0));
+        }
+        self.notify(app);
+    }
+
+    fn remove(&self, app: &tauri::AppHandle, id: &str) {
+        self.tasks.lock().unwrap().remove(id);
+        self.notify(app);
+    }
+
+    fn notify(&self, app: &tauri::AppHandle) {
+        let _ = app.emit("tasks-changed", self.list());
+        app_state::refresh(app);
+    }
+}
+
+pub struct TaskHandle {
+    app: tauri::AppHandle,
+    id: String,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl TaskHandle {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Runs `hook` when the task is cancelled, for work that is idle between calls
+    /// (e.g. waiting for the next chunk) and would otherwise never notice.
+    pub fn on_cancel(&self, hook: impl FnOnce() + Send + 'static) {
+        self.app
+            .state::<TaskRegistry>()
+            .set_cancel_hook(&self.id, Box::new(hook));
+    }
+
+    pub fn set_progress(&self, progress: f64) {
+        self.app
+            .state::<TaskRegistry>()
+            .set_progress(&self.app, &self.id, progress);
+    }
+}
+
+impl Drop for TaskHandle {
+    fn drop(&mut self) {
+        self.app.state::<TaskRegistry>().remove(&self.app, &self.id);
+    }
+}
+
+#[tauri::command]
+pub async fn list_active_tasks(tasks: tauri::State<'_, TaskRegistry>) -> Result<Vec<TaskInfo>, String> {
+    Ok(tasks.list())
+}
+
+#[tauri::command]
+pub async fn cancel_task(tasks: tauri::State<'_, TaskRegistry>, id: String) -> Result<(), String> {
+    tasks.cancel(&id)
+}
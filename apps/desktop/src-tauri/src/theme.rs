@@ -0,0 +1,159 @@
+//! Forwards OS theme changes as `system-theme-changed`. Some desktops fire several
+//! `ThemeChanged` events during an automatic dark-mode transition, so only the theme that is
+//! still current after a short quiet period is emitted, and only if it actually changed.
+//!
+//! The user's choice is stored as `dark`, `light` or `auto`; with `auto` no theme is forced
+//! and windows follow the OS.
+//!
+//! The OS accent color is read on Windows and macOS. Neither reports accent changes through a
+//! window event, so it is polled and `accent-color-changed` goes out when it differs.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tauri::{Emitter, Manager, Theme};
+
+use crate::{app_state, settings};
+
+const PREFERENCE_KEY: &str = "window_theme";
+const SETTLE_DELAY: Duration = Duration::from_millis(300);
+const ACCENT_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+#[derive(Default)]
+struct ThemeDebounce {
+    last_emitted: Mutex<Option<&'static str>>,
+    latest_event: AtomicU64,
+}
+
+fn theme_name(theme: Theme) -> &'static str {
+    match theme {
+        Theme::Dark => "dark",
+        Theme::Light => "light",
+        _ => "light",
+    }
+}
+
+/// The theme to force for a preference; `None` for `auto`.
+pub fn parse_preference(preference: &str) -> Result<Option<Theme>, String> {
+    match preference {
+        "dark" => Ok(Some(Theme::Dark)),
+        "light" => Ok(Some(Theme::Light)),
+        "auto" => Ok(None),
+        _ => Err(format!("invalid theme: {}", preference)),
+    }
+}
+
+pub fn save_preference(app: &tauri::AppHandle, preference: &str) -> Result<(), String> {
+    settings::set(app, PREFERENCE_KEY, preference)
+}
+
+/// Applies the stored preference app-wide. Runs before any window is shown, so none of them
+/// starts out in the wrong theme.
+pub fn apply_preference(app: &tauri::AppHandle) {
+    let preference = settings::get::<String>(app, PREFERENCE_KEY).unwrap_or_else(|| "auto".to_string());
+    match parse_preference(&preference) {
+        Ok(theme) => app.set_theme(theme),
+        Err(e) => log::warn!("Ignoring stored theme preference: {}", e),
+    }
+}
+
+pub fn watch(window: &tauri::WebviewWindow) {
+    let debounce = Arc::new(ThemeDebounce::default());
+    let window_clone = window.clone();
+    window.on_window_event(move |event| {
+        let tauri::WindowEvent::ThemeChanged(theme) = event else {
+            return;
+        };
+        let theme = theme_name(*theme);
+
+        {
+            // The first detection goes out immediately; there's nothing to debounce yet.
+            let mut last_emitted = debounce.last_emitted.lock().unwrap();
+            if last_emitted.is_none() {
+                *last_emitted = Some(theme);
+                let _ = window_clone.emit("system-theme-changed", theme);
+                app_state::refresh(window_clone.app_handle());
+                return;
+            }
+        }
+
+        let event_id = debounce.latest_event.fetch_add(1, Ordering::SeqCst) + 1;
+        let debounce = debounce.clone();
+        let window = window_clone.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(SETTLE_DELAY);
+            if debounce.latest_event.load(Ordering::SeqCst) != event_id {
+                return;
+            }
+            let mut last_emitted = debounce.last_emitted.lock().unwrap();
+            if *last_emitted != Some(theme) {
+                *last_emitted = Some(theme);
+                let _ = window.emit("system-theme-changed", theme);
+                app_state::refresh(window.app_handle());
+            }
+        });
+    });
+}
+
+fn hex(red: u8, green: u8, blue: u8) -> String {
+    format!("#{:02X}{:02X}{:02X}", red, green, blue)
+}
+
+#[cfg(windows)]
+fn accent_color() -> Option<String> {
+    use winreg::enums::{HKEY_CURRENT_USER, KEY_READ};
+    use winreg::RegKey;
+
+    // Stored as 0xAABBGGRR.
+    let value: u32 = RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey_with_flags("SOFTWARE\\Microsoft\\Windows\\DWM", KEY_READ)
+        .ok()?
+        .get_value("AccentColor")
+        .ok()?;
+    Some(hex(value as u8, (value >> 8) as u8, (value >> 16) as u8))
+}
+
+#[cfg(target_os = "macos")]
+fn accent_color() -> Option<String> {
+    use objc2_app_kit::{NSColor, NSColorSpace};
+
+    let color = NSColor::controlAccentColor().colorUsingColorSpace(&NSColorSpace::sRGBColorSpace())?;
+    let channel = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    Some(hex(
+        channel(color.redComponent()),
+        channel(color.greenComponent()),
+        channel(color.blueComponent()),
+    ))
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn accent_color() -> Option<String> {
+    None
+}
+
+/// Emits `accent-color-changed` with the new `#RRGGBB` (or `null`) whenever the OS accent changes.
+pub fn watch_accent(app: &tauri::AppHandle) {
+    if cfg!(not(any(windows, target_os = "macos"))) {
+        return;
+    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut last = accent_color();
+        loop {
+            std::thread::sleep(ACCENT_POLL_INTERVAL);
+            let current = accent_color();
+            if current != last {
+                let _ = app.emit("accent-color-changed", &current);
+                last = current;
+            }
+        }
+    });
+}
+
+/// The OS accent as `#RRGGBB`; `None` where there is none or it can't be read, so the UI
+/// falls back to the brand green.
+#[tauri::command]
+pub async fn get_accent_color(_window: tauri::Window) -> Result<Option<String>, String> {
+    Ok(accent_color())
+}
@@ -0,0 +1,165 @@
+//! Small PNG previews of a document's first page for the recent-documents grid.
+//! Text is drawn as a layout skeleton (headings, paragraphs, list items) rather than glyphs,
+//! which keeps rendering dependency-free and fast enough to run on every save.
+
+use std::fs;
+use std::path::Path;
+
+use crate::html::{self, BlockKind};
+use crate::{documents, paths};
+
+const MIN_SIZE: u32 = 64;
+const MAX_SIZE: u32 = 1024;
+
+const PAGE: [u8; 3] = [0xff, 0xff, 0xff];
+const BORDER: [u8; 3] = [0xd9, 0xd9, 0xd9];
+const HEADING: [u8; 3] = [0x00, 0x54, 0x37];
+const TEXT: [u8; 3] = [0xa6, 0xa6, 0xa6];
+const BULLET: [u8; 3] = [0x46, 0x96, 0x2b];
+
+#[tauri::command]
+pub async fn render_thumbnail(
+    app: tauri::AppHandle,
+    doc_id: String,
+    html: String,
+    size: u32,
+) -> Result<String, String> {
+    let doc_id = sanitize_doc_id(&doc_id)?;
+    let cache_dir = paths::cache_dir(&app)?.join("thumbnails");
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let size = size.clamp(MIN_SIZE, MAX_SIZE);
+        let hash = documents::hash_content(&html);
+        let path = cache_dir.join(format!("{}-{}-{}.png", doc_id, size, &hash[..16]));
+        if path.exists() {
+            return Ok(path.to_string_lossy().to_string());
+        }
+
+        fs::create_dir_all(&cache_dir).map_err(|e| e.to_string())?;
+        remove_stale(&cache_dir, &doc_id, size);
+
+        let (width, height, pixels) = render(&html, size);
+        write_png(&path, width, height, &pixels)?;
+        Ok(path.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn sanitize_doc_id(doc_id: &str) -> Result<String, String> {
+    let id: String = doc_id
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .take(64)
+        .collect();
+    if id.is_empty() {
+        return Err("invalid document id".to_string());
+    }
+    Ok(id)
+}
+
+/// The document id and size of a cached `<doc_id>-<size>-<hash>.png`. Ids may contain `-`
+/// themselves, so the name is split from the right.
+fn parse_cache_name(name: &str) -> Option<(&str, u32)> {
+    let mut parts = name.strip_suffix(".png")?.rsplitn(3, '-');
+    let _hash = parts.next()?;
+    let size = parts.next()?.parse().ok()?;
+    Some((parts.next()?, size))
+}
+
+fn remove_stale(cache_dir: &Path, doc_id: &str, size: u32) {
+    let Ok(entries) = fs::read_dir(cache_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        if parse_cache_name(&name.to_string_lossy()) == Some((doc_id, size)) {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Self {
+        let mut pixels = Vec::with_capacity((width * height * 3) as usize);
+        for _ in 0..width * height {
+            pixels.extend_from_slice(&PAGE);
+        }
+        Self { width, height, pixels }
+    }
+
+    fn fill(&mut self, x: u32, y: u32, w: u32, h: u32, color: [u8; 3]) {
+        for py in y..(y + h).min(self.height) {
+            for px in x..(x + w).min(self.width) {
+                let i = ((py * self.width + px) * 3) as usize;
+                self.pixels[i..i + 3].copy_from_slice(&color);
+            }
+        }
+    }
+}
+
+fn render(html: &str, width: u32) -> (u32, u32, Vec<u8>) {
+    // A4 aspect ratio, so the grid shows a recognisable page shape.
+    let height = width * 297 / 210;
+    let mut canvas = Canvas::new(width, height);
+
+    canvas.fill(0, 0, width, 1, BORDER);
+    canvas.fill(0, height - 1, width, 1, BORDER);
+    canvas.fill(0, 0, 1, height, BORDER);
+    canvas.fill(width - 1, 0, 1, height, BORDER);
+
+    let margin = (width / 10).max(4);
+    let content_width = width - 2 * margin;
+    let unit = (width / 96).max(1);
+    let line_height = unit * 3;
+    let bottom = height - margin;
+    let mut y = margin;
+
+    'blocks: for block in html::to_blocks(html) {
+        let (bar, color, indent) = match block.kind {
+            BlockKind::Heading(level) => (unit * (4 - level.min(3) as u32 + 1), HEADING, 0),
+            BlockKind::ListItem => (unit, TEXT, unit * 3),
+            BlockKind::Paragraph => (unit, TEXT, 0),
+        };
+        let advance = bar + line_height - unit;
+        if y + bar > bottom {
+            break;
+        }
+        if block.kind == BlockKind::ListItem {
+            canvas.fill(margin, y, unit, bar, BULLET);
+        }
+
+        let mut x = margin + indent;
+        for word in block.text.split_whitespace() {
+            let w = word.chars().count() as u32 * bar.div_ceil(2).max(unit);
+            let w = w.min(content_width - indent);
+            if x + w > margin + content_width {
+                x = margin + indent;
+                y += advance;
+                if y + bar > bottom {
+                    break 'blocks;
+                }
+            }
+            canvas.fill(x, y, w, bar, color);
+            x += w + unit * 2;
+        }
+        y += advance + line_height;
+    }
+
+    (canvas.width, canvas.height, canvas.pixels)
+}
+
+fn write_png(path: &Path, width: u32, height: u32, pixels: &[u8]) -> Result<(), String> {
+    let file = fs::File::create(path).map_err(|e| e.to_string())?;
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    writer.write_image_data(pixels).map_err(|e| e.to_string())
+}
@@ -0,0 +1,108 @@
+//! Window titles composed from a user-chosen template, so the taskbar shows which draft a
+//! window holds and whether it has unsaved changes. The frontend reports document name and
+//! dirty state per window; the title is recomposed whenever either or the template changes.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tauri::Manager;
+
+use crate::{settings, window};
+
+const FORMAT_KEY: &str = "title_format";
+const DEFAULT_FORMAT: &str = "{unsaved}{document} — {app}";
+const TOKENS: &[&str] = &["{document}", "{app}", "{unsaved}"];
+const UNSAVED_MARKER: &str = "● ";
+
+#[derive(Clone, Default)]
+struct DocumentTitle {
+    document: Option<String>,
+    unsaved: bool,
+}
+
+/// Last reported document state, by window label.
+#[derive(Default)]
+pub struct WindowTitles(Mutex<HashMap<String, DocumentTitle>>);
+
+/// Every `{…}` in `template` must be a known token.
+fn is_valid(template: &str) -> bool {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else { return false };
+        if !TOKENS.contains(&&rest[start..start + end + 1]) {
+            return false;
+        }
+        rest = &rest[start + end + 1..];
+    }
+    !template.trim().is_empty()
+}
+
+fn current_format(app: &tauri::AppHandle) -> String {
+    settings::get::<String>(app, FORMAT_KEY)
+        .filter(|template| is_valid(template))
+        .unwrap_or_else(|| DEFAULT_FORMAT.to_string())
+}
+
+fn compose(app: &tauri::AppHandle, template: &str, state: &DocumentTitle) -> String {
+    let app_name = app.config().product_name.clone().unwrap_or_else(|| "Grünerator".to_string());
+    // Without a document the separators of the template would dangle; show just the app.
+    let Some(document) = &state.document else {
+        return app_name;
+    };
+    template
+        .replace("{document}", document)
+        .replace("{app}", &app_name)
+        .replace("{unsaved}", if state.unsaved { UNSAVED_MARKER } else { "" })
+}
+
+fn apply(app: &tauri::AppHandle, label: &str, template: &str, state: &DocumentTitle) -> Result<(), String> {
+    let Some(target) = app.get_webview_window(label) else {
+        return Ok(());
+    };
+    let title = window::clean_title(&compose(app, template, state))?;
+    target.set_title(&title).map_err(|e| e.to_string())
+}
+
+/// Stores the template and retitles all windows. An empty template or one with unknown tokens
+/// falls back to the default. Returns the template in effect.
+#[tauri::command]
+pub async fn set_title_format(app: tauri::AppHandle, template: String) -> Result<String, String> {
+    let template = if is_valid(&template) {
+        template
+    } else {
+        log::warn!("Invalid title format {:?}, using the default", template);
+        DEFAULT_FORMAT.to_string()
+    };
+    settings::set(&app, FORMAT_KEY, &template)?;
+
+    let titles = app.state::<WindowTitles>().0.lock().unwrap().clone();
+    for (label, state) in titles {
+        apply(&app, &label, &template, &state)?;
+    }
+    Ok(template)
+}
+
+#[tauri::command]
+pub async fn get_title_format(app: tauri::AppHandle) -> Result<String, String> {
+    Ok(current_format(&app))
+}
+
+/// Reports the calling window's document; `None` when it shows no document.
+#[tauri::command]
+pub async fn set_document_title(
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    document: Option<String>,
+    unsaved: bool,
+) -> Result<(), String> {
+    let document = document
+        .map(|document| document.trim().to_string())
+        .filter(|document| !document.is_empty());
+    let state = DocumentTitle { document, unsaved };
+    app.state::<WindowTitles>()
+        .0
+        .lock()
+        .unwrap()
+        .insert(window.label().to_string(), state.clone());
+    apply(&app, window.label(), &current_format(&app), &state)
+}
@@ -0,0 +1,89 @@
+//! Touch mode for 2-in-1 laptops and tablets: the app menu drops its accelerators, which
+//! makes the entries shorter (native menus can't be given larger hit targets), and
+//! `touch-mode-changed` tells the frontend to enlarge its controls. Purely presentational.
+//!
+//! Until the user picks a side, touch mode follows the device: on when Windows reports a
+//! touch screen in tablet posture. Other platforms don't expose that, so it stays off there.
+
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+
+use crate::{app_state, menu, settings};
+
+const TOUCH_MODE_KEY: &str = "touch_mode";
+
+#[derive(Clone, Copy, Default, Serialize)]
+pub struct TouchSupport {
+    /// A touch digitizer is present; `None` where the platform can't tell.
+    capable: Option<bool>,
+    /// Touch is the main input right now (tablet posture).
+    primary: bool,
+}
+
+#[derive(Clone, Serialize)]
+pub struct TouchModeState {
+    enabled: bool,
+    /// Following the device rather than an explicit choice.
+    automatic: bool,
+    support: TouchSupport,
+}
+
+#[cfg(windows)]
+fn detect() -> TouchSupport {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetSystemMetrics, SM_CONVERTIBLESLATEMODE, SM_MAXIMUMTOUCHES,
+    };
+
+    let capable = unsafe { GetSystemMetrics(SM_MAXIMUMTOUCHES) } > 0;
+    // 0 means the convertible is in slate (tablet) mode.
+    let slate = unsafe { GetSystemMetrics(SM_CONVERTIBLESLATEMODE) } == 0;
+    TouchSupport {
+        capable: Some(capable),
+        primary: capable && slate,
+    }
+}
+
+#[cfg(not(windows))]
+fn detect() -> TouchSupport {
+    TouchSupport::default()
+}
+
+pub fn init(app: &tauri::AppHandle) {
+    let support = detect();
+    log::info!("Touch support: {:?}, primary: {}", support.capable, support.primary);
+    app.manage(support);
+}
+
+fn state(app: &tauri::AppHandle) -> TouchModeState {
+    let support = app.try_state::<TouchSupport>().map(|support| *support).unwrap_or_default();
+    let explicit: Option<bool> = settings::get(app, TOUCH_MODE_KEY);
+    TouchModeState {
+        enabled: explicit.unwrap_or(support.primary),
+        automatic: explicit.is_none(),
+        support,
+    }
+}
+
+pub fn is_enabled(app: &tauri::AppHandle) -> bool {
+    state(app).enabled
+}
+
+/// Turns touch mode on or off; `None` goes back to following the device.
+#[tauri::command]
+pub async fn set_touch_mode(app: tauri::AppHandle, enabled: Option<bool>) -> Result<TouchModeState, String> {
+    let before = is_enabled(&app);
+    settings::set(&app, TOUCH_MODE_KEY, enabled)?;
+    let after = state(&app);
+
+    if after.enabled != before {
+        menu::rebuild(&app).map_err(|e| e.to_string())?;
+        let _ = app.emit("touch-mode-changed", &after);
+        app_state::refresh(&app);
+    }
+    Ok(after)
+}
+
+#[tauri::command]
+pub async fn get_touch_mode(app: tauri::AppHandle) -> Result<TouchModeState, String> {
+    Ok(state(&app))
+}
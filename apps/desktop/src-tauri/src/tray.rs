@@ -0,0 +1,446 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::image::Image;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+use tauri::{Emitter, Manager, Wry};
+
+use crate::{documents, safe_mode, settings, startup, toggle_window_visibility};
+
+pub const TRAY_ID: &str = "main";
+
+const BUNDLED_ICON: &[u8] = include_bytes!("../icons/32x32.png");
+const RECENT_ID_PREFIX: &str = "tray_recent:";
+const MAX_RECENT: usize = 8;
+/// Namespaced so a generator type can never collide with the fixed `tray_*` items.
+const NEW_ID_PREFIX: &str = "tray_new_";
+const MAX_QUICK_ACTIONS: usize = 20;
+const ENABLED_KEY: &str = "tray_enabled";
+const LEFT_CLICK_KEY: &str = "tray_left_click";
+const LEFT_CLICK_ACTIONS: &[&str] = &["toggle", "show", "menu"];
+const BUSY_FRAME: Duration = Duration::from_millis(500);
+const BUSY_DOT: [u8; 4] = [0xff, 0xb4, 0x00, 0xff];
+const ERROR_DOT: [u8; 4] = [0xd0, 0x1c, 0x1c, 0xff];
+const DEFAULT_TOOLTIP: &str = "Grünerator";
+/// Windows cuts tray tooltips off at 127 characters.
+const MAX_TOOLTIP_CHARS: usize = 127;
+
+#[derive(Clone, Serialize)]
+struct TrayIconMissing {
+    fallback: &'static str,
+}
+
+#[derive(Clone, Deserialize)]
+pub struct RecentDoc {
+    id: String,
+    label: String,
+}
+
+/// The recent documents last handed over by the frontend, kept across menu rebuilds.
+#[derive(Default)]
+pub struct TrayRecent(Mutex<Vec<RecentDoc>>);
+
+/// A generator offered under "Neu erstellen"; `kind` is what `tray-new` reports.
+#[derive(Clone, Deserialize)]
+pub struct QuickAction {
+    #[serde(rename = "type")]
+    kind: String,
+    label: String,
+}
+
+#[derive(Default)]
+pub struct TrayQuickActions(Mutex<Vec<QuickAction>>);
+
+/// Bumped on every status change, so a running busy animation notices it is outdated.
+#[derive(Default)]
+pub struct TrayStatus(AtomicU64);
+
+/// Minimal builds may not embed a default window icon; tray creation must never take the rest
+/// of `setup` down with it, so fall back to the bundled PNG and finally to no icon at all.
+fn tray_icon(app: &tauri::AppHandle) -> Option<Image<'static>> {
+    if let Some(icon) = app.default_window_icon() {
+        return Some(icon.clone().to_owned());
+    }
+
+    log::warn!("No default window icon embedded, using bundled tray icon");
+    match Image::from_bytes(BUNDLED_ICON) {
+        Ok(icon) => {
+            startup::notify(app, "tray-icon-missing", TrayIconMissing { fallback: "bundled" });
+            Some(icon.to_owned())
+        }
+        Err(e) => {
+            log::warn!("Bundled tray icon unusable, tray will have no icon: {}", e);
+            startup::notify(app, "tray-icon-missing", TrayIconMissing { fallback: "none" });
+            None
+        }
+    }
+}
+
+fn build_menu(app: &tauri::AppHandle) -> tauri::Result<Menu<Wry>> {
+    let show_hide = MenuItem::with_id(app, "tray_show_hide", "Anzeigen/Verbergen", true, None::<&str>)?;
+    let separator = MenuItem::with_id(app, "tray_separator", "─────────────", false, None::<&str>)?;
+    let quit = MenuItem::with_id(app, "tray_quit", "Beenden", true, None::<&str>)?;
+
+    let tray_menu = Menu::with_items(app, &[&show_hide])?;
+    let quick_actions = app
+        .try_state::<TrayQuickActions>()
+        .map(|actions| actions.0.lock().unwrap().clone())
+        .unwrap_or_default();
+    if !quick_actions.is_empty() {
+        let new_menu = Submenu::new(app, "Neu erstellen", true)?;
+        for action in quick_actions {
+            new_menu.append(&MenuItem::with_id(
+                app,
+                format!("{}{}", NEW_ID_PREFIX, action.kind),
+                documents::menu_label(&action.label),
+                true,
+                None::<&str>,
+            )?)?;
+        }
+        tray_menu.append(&new_menu)?;
+    }
+    let pinned = documents::pinned(app);
+    if !pinned.is_empty() {
+        tray_menu.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+    for doc in pinned {
+        tray_menu.append(&MenuItem::with_id(
+            app,
+            format!("{}{}", documents::PINNED_ID_PREFIX, doc.id),
+            documents::menu_label(&doc.title),
+            true,
+            None::<&str>,
+        )?)?;
+    }
+    let recent = app
+        .try_state::<TrayRecent>()
+        .map(|recent| recent.0.lock().unwrap().clone())
+        .unwrap_or_default();
+    if !recent.is_empty() {
+        tray_menu.append_items(&[
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, "tray_recent_header", "Zuletzt verwendet", false, None::<&str>)?,
+        ])?;
+    }
+    for doc in recent {
+        tray_menu.append(&MenuItem::with_id(
+            app,
+            format!("{}{}", RECENT_ID_PREFIX, doc.id),
+            documents::menu_label(&doc.label),
+            true,
+            None::<&str>,
+        )?)?;
+    }
+    tray_menu.append_items(&[&separator, &quit])?;
+    Ok(tray_menu)
+}
+
+/// The icon the status variants are drawn on, without the startup notices of `tray_icon`.
+fn base_icon(app: &tauri::AppHandle) -> Option<Image<'static>> {
+    app.default_window_icon()
+        .map(|icon| icon.clone().to_owned())
+        .or_else(|| Image::from_bytes(BUNDLED_ICON).ok().map(Image::to_owned))
+}
+
+/// `icon` with a status dot in its lower right corner.
+fn with_dot(icon: &Image<'_>, color: [u8; 4]) -> Image<'static> {
+    let (width, height) = (icon.width() as usize, icon.height() as usize);
+    let mut rgba = icon.rgba().to_vec();
+    let radius = (width.min(height) as f64 / 5.0).max(2.0);
+    let (cx, cy) = (width as f64 - radius - 1.0, height as f64 - radius - 1.0);
+    for y in 0..height {
+        for x in 0..width {
+            let (dx, dy) = (x as f64 + 0.5 - cx, y as f64 + 0.5 - cy);
+            if dx * dx + dy * dy <= radius * radius {
+                let i = (y * width + x) * 4;
+                rgba[i..i + 4].copy_from_slice(&color);
+            }
+        }
+    }
+    Image::new_owned(rgba, width as u32, height as u32)
+}
+
+/// What a left click on the tray icon does: `toggle` the main window (the default), `show` it,
+/// or open the `menu` like a right click. Linux always opens the menu.
+fn left_click_action(app: &tauri::AppHandle) -> String {
+    settings::get::<String>(app, LEFT_CLICK_KEY)
+        .filter(|action| LEFT_CLICK_ACTIONS.contains(&action.as_str()))
+        .unwrap_or_else(|| "toggle".to_string())
+}
+
+/// Whether the user wants a tray icon at all; some rely on the dock or taskbar instead.
+pub fn is_enabled(app: &tauri::AppHandle) -> bool {
+    settings::get(app, ENABLED_KEY).unwrap_or(true)
+}
+
+pub fn rebuild_menu(app: &tauri::AppHandle) -> tauri::Result<()> {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        tray.set_menu(Some(build_menu(app)?))?;
+    }
+    Ok(())
+}
+
+pub fn create(app: &tauri::AppHandle) -> tauri::Result<TrayIcon> {
+    let tray_menu = build_menu(app)?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID);
+    if let Some(icon) = tray_icon(app) {
+        builder = builder.icon(icon);
+    }
+
+    builder
+        .tooltip(DEFAULT_TOOLTIP)
+        .menu(&tray_menu)
+        .show_menu_on_left_click(left_click_action(app) == "menu")
+        .on_menu_event(|app, event| {
+            match event.id.as_ref() {
+                "tray_show_hide" => toggle_window_visibility(app),
+                "tray_quit" => {
+                    app.exit(0);
+                }
+                id => {
+                    if let Some(doc_id) = id.strip_prefix(documents::PINNED_ID_PREFIX) {
+                        let _ = app.emit("menu-open-pinned", doc_id);
+                    } else if let Some(doc_id) = id.strip_prefix(RECENT_ID_PREFIX) {
+                        let _ = app.emit("tray-open-recent", doc_id);
+                    } else if let Some(kind) = id.strip_prefix(NEW_ID_PREFIX) {
+                        let _ = app.emit("tray-new", kind);
+                    }
+                }
+            }
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                let app = tray.app_handle();
+                match left_click_action(app).as_str() {
+                    "show" => {
+                        if let Some(window) = app.get_webview_window("main") {
+                            let _ = window.show();
+                            let _ = window.unminimize();
+                            let _ = window.set_focus();
+                        }
+                    }
+                    // The menu opens by itself, see `show_menu_on_left_click`.
+                    "menu" => {}
+                    _ => toggle_window_visibility(app),
+                }
+            }
+        })
+        .build(app)
+}
+
+/// Shows or removes the tray icon and remembers the choice. Hiding is refused while the main
+/// window is hidden, as the tray would be the only way to bring it back. In safe mode only the
+/// setting changes.
+#[tauri::command]
+pub async fn set_tray_visible(app: tauri::AppHandle, visible: bool) -> Result<(), String> {
+    if !visible {
+        let window_hidden = app
+            .get_webview_window("main")
+            .is_some_and(|window| !window.is_visible().unwrap_or(true));
+        if window_hidden {
+            return Err("window_hidden: show the main window before hiding the tray".to_string());
+        }
+    }
+    settings::set(&app, ENABLED_KEY, visible)?;
+    if safe_mode::is_active(&app) {
+        return Ok(());
+    }
+
+    if visible {
+        if app.tray_by_id(TRAY_ID).is_none() {
+            create(&app).map_err(|e| e.to_string())?;
+        }
+    } else if let Some(tray) = app.remove_tray_by_id(TRAY_ID) {
+        // Stops the busy animation, which holds on to the icon.
+        app.state::<TrayStatus>().0.fetch_add(1, Ordering::SeqCst);
+        let _ = tray.set_visible(false);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_tray_visible(app: tauri::AppHandle) -> Result<bool, String> {
+    Ok(is_enabled(&app))
+}
+
+/// Replaces the "Zuletzt verwendet" section of the tray menu; at most eight entries are shown.
+#[tauri::command]
+pub async fn update_tray_recent(app: tauri::AppHandle, items: Vec<RecentDoc>) -> Result<(), String> {
+    let items: Vec<RecentDoc> = items
+        .into_iter()
+        .filter(|item| !item.id.trim().is_empty() && !item.label.trim().is_empty())
+        .take(MAX_RECENT)
+        .collect();
+    *app.state::<TrayRecent>().0.lock().unwrap() = items;
+    rebuild_menu(&app).map_err(|e| e.to_string())
+}
+
+/// Replaces the generators under "Neu erstellen"; an empty list removes the submenu. Types are
+/// limited to letters, digits, `-` and `_`, as they become part of the menu ids.
+#[tauri::command]
+pub async fn set_tray_quick_actions(app: tauri::AppHandle, actions: Vec<QuickAction>) -> Result<(), String> {
+    if actions.len() > MAX_QUICK_ACTIONS {
+        return Err(format!("at most {} quick actions are allowed", MAX_QUICK_ACTIONS));
+    }
+    for action in &actions {
+        let valid = !action.kind.is_empty()
+            && action.kind.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid || action.label.trim().is_empty() {
+            return Err(format!("invalid quick action: {}", action.kind));
+        }
+    }
+    *app.state::<TrayQuickActions>().0.lock().unwrap() = actions;
+    rebuild_menu(&app).map_err(|e| e.to_string())
+}
+
+/// `idle` shows the plain icon, `busy` a blinking amber dot on it and `error` a red one.
+#[tauri::command]
+pub async fn set_tray_status(app: tauri::AppHandle, status: String) -> Result<(), String> {
+    if !["idle", "busy", "error"].contains(&status.as_str()) {
+        return Err(format!("invalid tray status: {}", status));
+    }
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return Ok(());
+    };
+    let base = base_icon(&app).ok_or_else(|| "tray_icon_missing".to_string())?;
+    let generation = app.state::<TrayStatus>().0.fetch_add(1, Ordering::SeqCst) + 1;
+
+    match status.as_str() {
+        "busy" => {
+            let frames = [with_dot(&base, BUSY_DOT), base];
+            tray.set_icon(Some(frames[0].clone())).map_err(|e| e.to_string())?;
+            std::thread::spawn(move || {
+                for frame in frames.iter().cycle().skip(1) {
+                    std::thread::sleep(BUSY_FRAME);
+                    if tray.app_handle().state::<TrayStatus>().0.load(Ordering::SeqCst) != generation {
+                        break;
+                    }
+                    let _ = tray.set_icon(Some(frame.clone()));
+                }
+            });
+            Ok(())
+        }
+        "error" => tray.set_icon(Some(with_dot(&base, ERROR_DOT))).map_err(|e| e.to_string()),
+        _ => tray.set_icon(Some(base)).map_err(|e| e.to_string()),
+    }
+}
+
+#[tauri::command]
+pub async fn set_tray_left_click_action(app: tauri::AppHandle, action: String) -> Result<(), String> {
+    if !LEFT_CLICK_ACTIONS.contains(&action.as_str()) {
+        return Err(format!("invalid tray action: {}", action));
+    }
+    settings::set(&app, LEFT_CLICK_KEY, &action)?;
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        tray.set_show_menu_on_left_click(action == "menu").map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_tray_left_click_action(app: tauri::AppHandle) -> Result<String, String> {
+    Ok(left_click_action(&app))
+}
+
+/// Sets the tray tooltip, e.g. "Grünerator – 2 Entwürfe ungespeichert"; an empty one restores
+/// the default. Does nothing when there is no tray (safe mode).
+#[tauri::command]
+pub async fn set_tray_tooltip(app: tauri::AppHandle, tooltip: String) -> Result<(), String> {
+    let tooltip = tooltip.trim();
+    if tooltip.chars().count() > MAX_TOOLTIP_CHARS {
+        return Err(format!("tooltip_too_long: at most {} characters", MAX_TOOLTIP_CHARS));
+    }
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return Ok(());
+    };
+    let tooltip = if tooltip.is_empty() { DEFAULT_TOOLTIP } else { tooltip };
+    tray.set_tooltip(Some(tooltip)).map_err(|e| e.to_string())
+}
+
+/// 3×5 pixel digits for the taskbar overlay, one row per byte (bit 2 is the left column).
+#[cfg(windows)]
+const BADGE_GLYPHS: [[u8; 5]; 11] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+    // "+", for counts above 9.
+    [0b000, 0b010, 0b111, 0b010, 0b000],
+];
+
+/// A 16×16 red disc with the count in white, as Windows has no native badge.
+#[cfg(windows)]
+fn badge_image(count: u32) -> Image<'static> {
+    const SIZE: usize = 16;
+    const SCALE: usize = 2;
+    let glyphs: Vec<usize> = if count > 9 { vec![9, 10] } else { vec![count as usize] };
+
+    let mut rgba = vec![0u8; SIZE * SIZE * 4];
+    let mut put = |x: usize, y: usize, color: [u8; 4]| {
+        let i = (y * SIZE + x) * 4;
+        rgba[i..i + 4].copy_from_slice(&color);
+    };
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let (dx, dy) = (x as f64 - 7.5, y as f64 - 7.5);
+            if dx * dx + dy * dy <= 64.0 {
+                put(x, y, [0xd0, 0x1c, 0x1c, 0xff]);
+            }
+        }
+    }
+    let width = glyphs.len() * 3 * SCALE + (glyphs.len() - 1) * SCALE;
+    let (left, top) = ((SIZE - width) / 2, (SIZE - 5 * SCALE) / 2);
+    for (n, glyph) in glyphs.iter().enumerate() {
+        let origin = left + n * 4 * SCALE;
+        for (row, bits) in BADGE_GLYPHS[*glyph].iter().enumerate() {
+            for column in 0..3 {
+                if bits & (0b100 >> column) == 0 {
+                    continue;
+                }
+                for (sx, sy) in (0..SCALE).flat_map(|sx| (0..SCALE).map(move |sy| (sx, sy))) {
+                    put(origin + column * SCALE + sx, top + row * SCALE + sy, [0xff; 4]);
+                }
+            }
+        }
+    }
+    Image::new_owned(rgba, SIZE as u32, SIZE as u32)
+}
+
+/// Shows `count` on the app's taskbar or Dock icon: the Dock badge on macOS, an overlay icon
+/// on Windows and the launcher count on Linux desktops that support it (Unity, KDE, Dash to
+/// Dock). `None` or `0` clears it. Where no badge can be shown this does nothing.
+#[tauri::command]
+pub async fn set_tray_badge(app: tauri::AppHandle, count: Option<u32>) -> Result<(), String> {
+    let Some(main_window) = app.get_webview_window("main") else {
+        return Ok(());
+    };
+    let count = count.filter(|count| *count > 0);
+    #[cfg(windows)]
+    {
+        main_window
+            .set_overlay_icon(count.map(badge_image))
+            .map_err(|e| e.to_string())
+    }
+    #[cfg(not(windows))]
+    {
+        if let Err(e) = main_window.set_badge_count(count.map(i64::from)) {
+            log::debug!("Badge not supported here: {}", e);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,346 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{Emitter, Listener, Manager};
+use tauri_plugin_updater::{Update, Updater, UpdaterExt};
+use url::Url;
+
+use crate::tasks::{TaskKind, TaskRegistry};
+use crate::{dnd, paths, plugins, settings, startup};
+
+const LAST_CHECK_KEY: &str = "last_update_check";
+const CHANNEL_KEY: &str = "update_channel";
+const SKIPPED_KEY: &str = "skipped_update_version";
+const INTERVAL_KEY: &str = "update_check_interval_hours";
+const DEFAULT_INTERVAL_HOURS: u32 = 24;
+const MAX_INTERVAL_HOURS: u32 = 24 * 30;
+/// How long `relaunch_app` waits for the frontend to save its drafts.
+const RELAUNCH_GRACE: Duration = Duration::from_millis(500);
+/// How often the background worker looks whether a check is due; also the wait for the splash.
+const SCHEDULER_TICK: Duration = Duration::from_secs(60);
+const CHANNELS: &[&str] = &["stable", "beta"];
+/// Stable uses the endpoint from `tauri.conf.json`.
+const BETA_ENDPOINT: &str = "https://gruenerator.eu/api/releases/updater/beta.json";
+
+#[derive(Clone, Serialize)]
+pub struct UpdateCheckResult {
+    available: bool,
+    version: Option<String>,
+    current_version: String,
+    body: Option<String>,
+    /// The channel that was checked.
+    channel: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateCheckOutcome {
+    Available,
+    UpToDate,
+    Failed,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct LastUpdateCheck {
+    checked_at: String,
+    outcome: UpdateCheckOutcome,
+    version: Option<String>,
+    error: Option<String>,
+}
+
+fn channel(app: &tauri::AppHandle) -> String {
+    settings::get::<String>(app, CHANNEL_KEY)
+        .filter(|channel| CHANNELS.contains(&channel.as_str()))
+        .unwrap_or_else(|| "stable".to_string())
+}
+
+/// An updater pointed at the manifest of `channel`.
+fn updater(app: &tauri::AppHandle, channel: &str) -> Result<Updater, String> {
+    plugins::require(app, plugins::UPDATER)?;
+    let mut builder = app.updater_builder();
+    if channel == "beta" {
+        let endpoint = Url::parse(BETA_ENDPOINT).map_err(|e| e.to_string())?;
+        builder = builder.endpoints(vec![endpoint]).map_err(|e| e.to_string())?;
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// Runs an update check and records when it happened and how it went, whether it was
+/// triggered from the UI or in the background.
+pub async fn check(app: &tauri::AppHandle) -> Result<UpdateCheckResult, String> {
+    let result = run_check(app).await;
+
+    let record = match &result {
+        Ok(update) => LastUpdateCheck {
+            checked_at: chrono::Utc::now().to_rfc3339(),
+            outcome: if update.available {
+                UpdateCheckOutcome::Available
+            } else {
+                UpdateCheckOutcome::UpToDate
+            },
+            version: update.version.clone(),
+            error: None,
+        },
+        Err(e) => LastUpdateCheck {
+            checked_at: chrono::Utc::now().to_rfc3339(),
+            outcome: UpdateCheckOutcome::Failed,
+            version: None,
+            error: Some(e.clone()),
+        },
+    };
+    let _ = settings::set(app, LAST_CHECK_KEY, record);
+
+    result
+}
+
+async fn run_check(app: &tauri::AppHandle) -> Result<UpdateCheckResult, String> {
+    let current_version = app.package_info().version.to_string();
+    let channel = channel(app);
+    let updater = updater(app, &channel)?;
+
+    let skipped = settings::get::<String>(app, SKIPPED_KEY);
+    match updater.check().await {
+        // Only that exact version is skipped; anything newer is offered again.
+        Ok(Some(update)) if skipped.as_deref() != Some(update.version.as_str()) => {
+            Ok(UpdateCheckResult {
+                available: true,
+                version: Some(update.version.clone()),
+                current_version,
+                body: update.body.clone(),
+                channel,
+            })
+        }
+        Ok(_) => {
+            Ok(UpdateCheckResult {
+                available: false,
+                version: None,
+                current_version,
+                body: None,
+                channel,
+            })
+        }
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn interval_hours(app: &tauri::AppHandle) -> u32 {
+    settings::get(app, INTERVAL_KEY)
+        .filter(|hours| (1..=MAX_INTERVAL_HOURS).contains(hours))
+        .unwrap_or(DEFAULT_INTERVAL_HOURS)
+}
+
+/// Whether the last check, successful or not, is older than the configured interval.
+fn is_check_due(app: &tauri::AppHandle) -> bool {
+    let last = settings::get::<LastUpdateCheck>(app, LAST_CHECK_KEY)
+        .and_then(|last| chrono::DateTime::parse_from_rfc3339(&last.checked_at).ok());
+    let Some(last) = last else { return true };
+    let elapsed = chrono::Utc::now().signed_duration_since(last);
+    elapsed >= chrono::Duration::hours(interval_hours(app) as i64)
+}
+
+/// Checks for updates in the background whenever the interval has passed, emitting
+/// `update-available` with the check result when there is one. The first check waits until
+/// the main window is shown, so it doesn't compete with startup. While "Nicht stören" is on,
+/// a found update is held back and announced once it is turned off.
+pub fn init(app: &tauri::AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        while !startup::is_marked(&app, startup::MAIN_SHOWN) {
+            std::thread::sleep(Duration::from_secs(1));
+        }
+        let mut pending: Option<UpdateCheckResult> = None;
+        loop {
+            if is_check_due(&app) {
+                match tauri::async_runtime::block_on(check(&app)) {
+                    Ok(result) if result.available => pending = Some(result),
+                    // Skipped in the meantime, or already installed.
+                    Ok(_) => pending = None,
+                    Err(e) => log::warn!("Background update check failed: {}", e),
+                }
+            }
+            if !dnd::is_enabled(&app) {
+                if let Some(result) = pending.take() {
+                    let _ = app.emit("update-available", result);
+                }
+            }
+            std::thread::sleep(SCHEDULER_TICK);
+        }
+    });
+}
+
+/// What the updater replaces: the `.app` bundle's folder on macOS, the AppImage's folder on
+/// Linux, the executable's folder elsewhere.
+fn install_dir() -> Option<PathBuf> {
+    #[cfg(target_os = "linux")]
+    if let Some(appimage) = std::env::var_os("APPIMAGE") {
+        return PathBuf::from(appimage).parent().map(PathBuf::from);
+    }
+    let exe = std::env::current_exe().ok()?;
+    #[cfg(target_os = "macos")]
+    if let Some(bundle) = exe
+        .ancestors()
+        .nth(3)
+        .filter(|dir| dir.extension().is_some_and(|ext| ext == "app"))
+    {
+        return bundle.parent().map(PathBuf::from);
+    }
+    exe.parent().map(PathBuf::from)
+}
+
+/// Fails with `install_readonly: <path>` when the updater couldn't replace the installed app,
+/// e.g. on managed machines where only IT may install software.
+fn ensure_install_writable() -> Result<(), String> {
+    let Some(dir) = install_dir() else { return Ok(()) };
+    paths::check_writable(&dir).map_err(|e| {
+        log::debug!("Install dir {} not writable: {}", dir.display(), e);
+        format!("install_readonly: {}", dir.display())
+    })
+}
+
+/// Emits `install-not-writable` at startup so the UI can explain that updates need IT.
+pub fn check_install_writable(app: &tauri::AppHandle) {
+    if ensure_install_writable().is_err() {
+        let path = install_dir().unwrap_or_default();
+        log::warn!("Install dir {} is read-only, updates can't be installed", path.display());
+        startup::notify(app, "install-not-writable", json!({ "path": path }));
+    }
+}
+
+#[tauri::command]
+pub async fn check_for_update(app: tauri::AppHandle) -> Result<UpdateCheckResult, String> {
+    check(&app).await
+}
+
+/// Hours between background update checks, 1 to 720.
+#[tauri::command]
+pub async fn set_update_check_interval(app: tauri::AppHandle, hours: u32) -> Result<(), String> {
+    if !(1..=MAX_INTERVAL_HOURS).contains(&hours) {
+        return Err(format!("interval must be between 1 and {} hours", MAX_INTERVAL_HOURS));
+    }
+    settings::set(&app, INTERVAL_KEY, hours)
+}
+
+#[tauri::command]
+pub async fn get_update_check_interval(app: tauri::AppHandle) -> Result<u32, String> {
+    Ok(interval_hours(&app))
+}
+
+/// When the last check ran, background or not, as RFC 3339; `None` before the first one.
+#[tauri::command]
+pub async fn get_last_update_check(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    Ok(settings::get::<LastUpdateCheck>(&app, LAST_CHECK_KEY).map(|last| last.checked_at))
+}
+
+/// Tells a bad signature, meaning the download must not be trusted, apart from failures
+/// that are worth retrying.
+fn install_error(e: tauri_plugin_updater::Error) -> String {
+    use tauri_plugin_updater::Error;
+    match e {
+        Error::Minisign(_) | Error::Base64(_) | Error::SignatureUtf8(_) => format!("signature_invalid: {}", e),
+        Error::Reqwest(_) | Error::Network(_) | Error::ReleaseNotFound => format!("network_error: {}", e),
+        e => e.to_string(),
+    }
+}
+
+/// Downloads and installs `update` as a download task, emitting `update-download-progress`
+/// with `{ downloaded, total }` in bytes (`total` is `null` if the server doesn't say).
+async fn install(app: &tauri::AppHandle, update: Update) -> Result<(), String> {
+    let task = app.state::<TaskRegistry>().start(
+        app,
+        TaskKind::Download,
+        format!("Update {}", update.version),
+        false,
+    );
+    let mut downloaded = 0u64;
+    update
+        .download_and_install(
+            |chunk, total| {
+                downloaded += chunk as u64;
+                if let Some(total) = total.filter(|total| *total > 0) {
+                    task.set_progress(downloaded as f64 / total as f64);
+                }
+                let _ = app.emit(
+                    "update-download-progress",
+                    json!({ "downloaded": downloaded, "total": total }),
+                );
+            },
+            || {},
+        )
+        .await
+        .map_err(install_error)
+}
+
+/// Downloads and installs the available update, tracked as a download task, and emits
+/// `update-ready` with the version once installed, leaving the relaunch to the frontend. On
+/// Windows the installer takes over and quits the app itself, so `update-ready` only arrives on
+/// macOS and Linux. Errors are `install_readonly: <path>` (checked before downloading),
+/// `no_update`, `signature_invalid: …` and `network_error: …`.
+#[tauri::command]
+pub async fn download_and_install_update(app: tauri::AppHandle) -> Result<(), String> {
+    ensure_install_writable()?;
+
+    let updater = updater(&app, &channel(&app))?;
+    let update = updater
+        .check()
+        .await
+        .map_err(install_error)?
+        .ok_or_else(|| "no_update".to_string())?;
+    let version = update.version.clone();
+    install(&app, update).await?;
+    let _ = app.emit("update-ready", json!({ "version": version }));
+    Ok(())
+}
+
+/// Same as [`download_and_install_update`].
+#[tauri::command]
+pub async fn download_and_install(app: tauri::AppHandle) -> Result<(), String> {
+    download_and_install_update(app).await
+}
+
+/// `channel` is `stable` or `beta`; applies from the next check.
+#[tauri::command]
+pub async fn set_update_channel(app: tauri::AppHandle, channel: String) -> Result<(), String> {
+    if !CHANNELS.contains(&channel.as_str()) {
+        return Err(format!("unknown update channel: {}", channel));
+    }
+    settings::set(&app, CHANNEL_KEY, channel)
+}
+
+#[tauri::command]
+pub async fn get_update_channel(app: tauri::AppHandle) -> Result<String, String> {
+    Ok(channel(&app))
+}
+
+/// Hides `version` from `check_for_update` ("Skip this version").
+#[tauri::command]
+pub async fn skip_update_version(app: tauri::AppHandle, version: String) -> Result<(), String> {
+    let version = version.trim();
+    if version.is_empty() {
+        return Err("version is required".to_string());
+    }
+    settings::set(&app, SKIPPED_KEY, version)
+}
+
+#[tauri::command]
+pub async fn clear_skipped_version(app: tauri::AppHandle) -> Result<(), String> {
+    settings::remove(&app, SKIPPED_KEY)
+}
+
+/// Restarts the app, e.g. after `update-ready`. Emits `before-relaunch` first and gives the
+/// frontend up to half a second to flush unsaved drafts and answer with `relaunch-ready`.
+#[tauri::command]
+pub async fn relaunch_app(app: tauri::AppHandle) -> Result<(), String> {
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+    let listener = app.once("relaunch-ready", move |_| {
+        let _ = ready_tx.send(());
+    });
+    app.emit("before-relaunch", ()).map_err(|e| e.to_string())?;
+    let waited = tauri::async_runtime::spawn_blocking(move || ready_rx.recv_timeout(RELAUNCH_GRACE)).await;
+    if !matches!(waited, Ok(Ok(()))) {
+        log::info!("Frontend didn't confirm the relaunch in time, restarting anyway");
+        app.unlisten(listener);
+    }
+    app.restart()
+}
@@ -0,0 +1,149 @@
+//! Detects a main window that never finishes loading (bundled assets missing, dev server or
+//! remote URL unreachable). Instead of a permanently white window, the main window is hidden
+//! again and the splash screen shows a "Neu laden" error state driving `reload_webview`.
+//! Without a splash (`disable_splash`), the first completed load reveals the main window.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::webview::{PageLoadEvent, PageLoadPayload};
+use tauri::{Emitter, Manager, Url};
+
+use crate::{app_state, safe_mode, splash, startup, window};
+
+const LOAD_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Clone, Serialize)]
+pub struct LoadError {
+    url: String,
+    error: String,
+}
+
+#[derive(Default)]
+pub struct PageLoad {
+    started: AtomicU64,
+    finished: AtomicU64,
+    /// The page the main window is meant to show, i.e. the last non-error navigation.
+    url: Mutex<Option<Url>>,
+    /// The first page it loaded: the app's start URL.
+    start_url: Mutex<Option<Url>>,
+    error: Mutex<Option<LoadError>>,
+}
+
+pub fn load_failed(app: &tauri::AppHandle) -> bool {
+    app.state::<PageLoad>().error.lock().unwrap().is_some()
+}
+
+/// Chromium-based webviews (WebView2) replace a failed navigation with their own error page.
+fn is_error_page(url: &Url) -> bool {
+    url.scheme() == "chrome-error"
+}
+
+pub fn on_page_load(webview: &tauri::Webview, payload: &PageLoadPayload<'_>) {
+    if webview.label() != "main" {
+        return;
+    }
+    let app = webview.app_handle().clone();
+    let state = app.state::<PageLoad>();
+    let url = payload.url().clone();
+
+    match payload.event() {
+        PageLoadEvent::Started => {
+            let navigation = state.started.fetch_add(1, Ordering::SeqCst) + 1;
+            if !is_error_page(&url) {
+                *state.url.lock().unwrap() = Some(url.clone());
+                state.start_url.lock().unwrap().get_or_insert_with(|| url.clone());
+            }
+            std::thread::spawn(move || {
+                std::thread::sleep(LOAD_TIMEOUT);
+                let state = app.state::<PageLoad>();
+                if state.started.load(Ordering::SeqCst) == navigation
+                    && state.finished.load(Ordering::SeqCst) < navigation
+                {
+                    fail(&app, url.to_string(), "timeout");
+                }
+            });
+        }
+        PageLoadEvent::Finished => {
+            state
+                .finished
+                .store(state.started.load(Ordering::SeqCst), Ordering::SeqCst);
+            if is_error_page(&url) {
+                let intended = state.url.lock().unwrap().clone();
+                let url = intended.map(|url| url.to_string()).unwrap_or_else(|| url.to_string());
+                std::thread::spawn(move || fail(&app, url, "navigation_failed"));
+            } else if state.error.lock().unwrap().take().is_some() {
+                // A retry succeeded: leave the error screen.
+                log::info!("Main window loaded after retry");
+                splash::finish(&app);
+                app_state::refresh(&app);
+            } else if splash::is_disabled(&app) && !startup::is_marked(&app, startup::MAIN_SHOWN) {
+                splash::finish(&app);
+            }
+        }
+    }
+}
+
+fn fail(app: &tauri::AppHandle, url: String, error: &str) {
+    log::error!("Main window failed to load {}: {}", url, error);
+    let load_error = LoadError {
+        url,
+        error: error.to_string(),
+    };
+    *app.state::<PageLoad>().error.lock().unwrap() = Some(load_error.clone());
+
+    if let Some(main_window) = app.get_webview_window("main") {
+        let _ = main_window.hide();
+    }
+    // The splash may already be gone, or disabled; bring it back to host the error.
+    if let Some(splashscreen) = splash::open(app) {
+        let _ = splashscreen.show();
+    }
+
+    let _ = app.emit("webview-load-failed", load_error);
+    app_state::refresh(app);
+}
+
+/// For screens created after the failure, which missed `webview-load-failed`.
+#[tauri::command]
+pub async fn get_webview_load_error(app: tauri::AppHandle) -> Result<Option<LoadError>, String> {
+    Ok(app.state::<PageLoad>().error.lock().unwrap().clone())
+}
+
+#[tauri::command]
+pub async fn reload_webview(app: tauri::AppHandle) -> Result<(), String> {
+    let main_window = app
+        .get_webview_window("main")
+        .ok_or_else(|| "main window not found".to_string())?;
+    let url = app.state::<PageLoad>().url.lock().unwrap().clone();
+    match url {
+        Some(url) => main_window.navigate(url).map_err(|e| e.to_string()),
+        None => main_window.reload().map_err(|e| e.to_string()),
+    }
+}
+
+/// Restarts the frontend without restarting the app: the main window goes back to the start
+/// URL, which drops all in-memory UI state, while the process, windows and native state stay.
+/// The UI density zoom is applied again in case the platform resets it on navigation.
+pub fn reload_frontend(app: &tauri::AppHandle) -> Result<(), String> {
+    let main_window = app
+        .get_webview_window("main")
+        .ok_or_else(|| "main window not found".to_string())?;
+    let start_url = app.state::<PageLoad>().start_url.lock().unwrap().clone();
+    log::info!("Soft reload of the frontend");
+    match start_url {
+        Some(url) => main_window.navigate(url).map_err(|e| e.to_string())?,
+        None => main_window.reload().map_err(|e| e.to_string())?,
+    }
+    if !safe_mode::is_active(app) {
+        window::apply_ui_density(app);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn soft_reload(app: tauri::AppHandle) -> Result<(), String> {
+    reload_frontend(&app)
+}
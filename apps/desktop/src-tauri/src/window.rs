@@ -0,0 +1,578 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager};
+
+use crate::{app_state, dnd, menu, print, quick_capture, settings, splash};
+
+const CONTENT_PROTECTION_KEY: &str = "content_protection";
+
+/// Whether the app's windows are currently excluded from screen capture. Windows created while
+/// it is set are protected as well (see [`apply_content_protection`]). Anything that captures
+/// window contents must refuse while this is set.
+#[derive(Default)]
+pub struct ContentProtection(AtomicBool);
+
+/// Protects a newly created window if protection is on; called for every webview when it is
+/// created.
+pub fn apply_content_protection(window: &tauri::Window) {
+    let active = window
+        .try_state::<ContentProtection>()
+        .is_some_and(|protection| protection.0.load(Ordering::SeqCst));
+    if active {
+        if let Err(e) = window.set_content_protected(true) {
+            log::warn!("Failed to protect window {}: {}", window.label(), e);
+        }
+    }
+}
+
+/// Turns protection on or off for all windows, e.g. when the frontend opens or leaves a
+/// document; with `doc_id` the choice is remembered for that document.
+#[tauri::command]
+pub async fn set_content_protected(
+    app: tauri::AppHandle,
+    protection: tauri::State<'_, ContentProtection>,
+    enabled: bool,
+    doc_id: Option<String>,
+) -> Result<(), String> {
+    if cfg!(not(any(target_os = "windows", target_os = "macos"))) {
+        return Err("unsupported".to_string());
+    }
+
+    for window in app.webview_windows().values() {
+        window.set_content_protected(enabled).map_err(|e| e.to_string())?;
+    }
+    protection.0.store(enabled, Ordering::SeqCst);
+
+    if let Some(doc_id) = doc_id {
+        let mut documents: HashMap<String, bool> =
+            settings::get(&app, CONTENT_PROTECTION_KEY).unwrap_or_default();
+        if enabled {
+            documents.insert(doc_id, true);
+        } else {
+            documents.remove(&doc_id);
+        }
+        settings::set(&app, CONTENT_PROTECTION_KEY, documents)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_content_protected(app: tauri::AppHandle, doc_id: String) -> Result<bool, String> {
+    let documents: HashMap<String, bool> =
+        settings::get(&app, CONTENT_PROTECTION_KEY).unwrap_or_default();
+    Ok(documents.get(&doc_id).copied().unwrap_or(false))
+}
+
+const UI_DENSITY_KEY: &str = "ui_density";
+const UI_DENSITY_PRESETS: &[(&str, f64)] = &[("compact", 0.9), ("comfortable", 1.0), ("spacious", 1.15)];
+
+#[derive(Clone, Serialize)]
+struct UiDensity {
+    preset: String,
+    zoom: f64,
+}
+
+fn density_zoom(preset: &str) -> Option<f64> {
+    UI_DENSITY_PRESETS
+        .iter()
+        .find(|(name, _)| *name == preset)
+        .map(|(_, zoom)| *zoom)
+}
+
+pub fn ui_density(app: &tauri::AppHandle) -> String {
+    settings::get(app, UI_DENSITY_KEY).unwrap_or_else(|| "comfortable".to_string())
+}
+
+pub fn apply_ui_density(app: &tauri::AppHandle) {
+    if let (Some(zoom), Some(main_window)) = (density_zoom(&ui_density(app)), app.get_webview_window("main")) {
+        let _ = main_window.set_zoom(zoom);
+    }
+}
+
+#[tauri::command]
+pub async fn set_ui_density(app: tauri::AppHandle, preset: String) -> Result<(), String> {
+    let zoom = density_zoom(&preset).ok_or_else(|| format!("unknown density preset: {}", preset))?;
+    if let Some(main_window) = app.get_webview_window("main") {
+        main_window.set_zoom(zoom).map_err(|e| e.to_string())?;
+    }
+    settings::set(&app, UI_DENSITY_KEY, &preset)?;
+    let _ = app.emit("ui-density-changed", UiDensity { preset, zoom });
+    app_state::refresh(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_ui_density(app: tauri::AppHandle) -> Result<String, String> {
+    Ok(ui_density(&app))
+}
+
+#[derive(Clone, Serialize)]
+struct ScaleFactorChange {
+    scale_factor: f64,
+    /// Display name as reported by the OS, or its position when it has none.
+    monitor: Option<String>,
+}
+
+/// Emits `scale-factor-changed` when the window moves to a display with a different DPI so
+/// the frontend can recompute its layout and zoom.
+pub fn watch_scale_factor(window: &tauri::WebviewWindow) {
+    let window_clone = window.clone();
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::ScaleFactorChanged { scale_factor, .. } = event {
+            let monitor = window_clone.current_monitor().ok().flatten().map(|monitor| {
+                monitor.name().cloned().unwrap_or_else(|| {
+                    let position = monitor.position();
+                    format!("{},{}", position.x, position.y)
+                })
+            });
+            let _ = window_clone.emit(
+                "scale-factor-changed",
+                ScaleFactorChange {
+                    scale_factor: *scale_factor,
+                    monitor,
+                },
+            );
+            app_state::refresh(window_clone.app_handle());
+        }
+    });
+}
+
+/// Windows that aren't documents and so never take part in `cycle_window`.
+const CYCLE_EXCLUDED: &[&str] = &[splash::WINDOW_LABEL, quick_capture::WINDOW_LABEL, print::WINDOW_LABEL];
+/// Presses closer together than this continue one cycle over the same order, like holding
+/// Alt while tabbing; after a pause the window reached counts as the most recently used.
+const CYCLE_STREAK: Duration = Duration::from_millis(1500);
+
+#[derive(Default)]
+struct WindowOrderState {
+    /// Window labels, most recently focused first.
+    recent: Vec<String>,
+    /// The order of the cycle in progress and when it last moved.
+    streak: Option<(Vec<String>, Instant)>,
+}
+
+#[derive(Default)]
+pub struct WindowOrder(Mutex<WindowOrderState>);
+
+/// Windows flashed by `flash_window` whose attention request is still pending.
+#[derive(Default)]
+pub struct AttentionRequests(Mutex<HashSet<String>>);
+
+/// Keeps the most-recently-used order behind `cycle_window` up to date, and ends a pending
+/// attention request once its window is focused.
+pub fn track_focus(window: &tauri::Window, event: &tauri::WindowEvent) {
+    let label = window.label();
+    if let tauri::WindowEvent::Focused(true) | tauri::WindowEvent::Destroyed = event {
+        let cleared = window.state::<AttentionRequests>().0.lock().unwrap().remove(label);
+        if cleared && matches!(event, tauri::WindowEvent::Focused(true)) {
+            let _ = window.request_user_attention(None);
+        }
+    }
+    if CYCLE_EXCLUDED.contains(&label) {
+        return;
+    }
+    let state = window.state::<WindowOrder>();
+    let mut order = state.0.lock().unwrap();
+    match event {
+        tauri::WindowEvent::Focused(true) => {
+            order.recent.retain(|recent| recent != label);
+            order.recent.insert(0, label.to_string());
+        }
+        tauri::WindowEvent::Destroyed => order.recent.retain(|recent| recent != label),
+        _ => {}
+    }
+}
+
+/// Focuses the next or previous document window in most-recently-used order and returns its
+/// label. Minimized and hidden windows are skipped unless there is nothing else to go to.
+pub fn cycle(app: &tauri::AppHandle, direction: &str) -> Result<String, String> {
+    let step: isize = match direction {
+        "next" => 1,
+        "prev" => -1,
+        _ => return Err(format!("unknown_direction: {}", direction)),
+    };
+    let windows = app.webview_windows();
+    let state = app.state::<WindowOrder>();
+    let mut order = state.0.lock().unwrap();
+    let now = Instant::now();
+
+    let mut labels = match order.streak.take() {
+        Some((labels, at)) if now.duration_since(at) < CYCLE_STREAK => labels,
+        _ => {
+            // Windows that were never focused go last, in a stable order.
+            let mut never_focused: Vec<String> =
+                windows.keys().filter(|label| !order.recent.contains(label)).cloned().collect();
+            never_focused.sort();
+            order.recent.iter().cloned().chain(never_focused).collect()
+        }
+    };
+    labels.retain(|label| windows.contains_key(label) && !CYCLE_EXCLUDED.contains(&label.as_str()));
+
+    let on_screen = |label: &&String| {
+        let window = &windows[label.as_str()];
+        window.is_visible().unwrap_or(false) && !window.is_minimized().unwrap_or(false)
+    };
+    let mut candidates: Vec<&String> = labels.iter().filter(on_screen).collect();
+    if candidates.is_empty() {
+        candidates = labels.iter().collect();
+    }
+    if candidates.is_empty() {
+        return Err("no_window".to_string());
+    }
+
+    let count = candidates.len() as isize;
+    let focused = candidates
+        .iter()
+        .position(|label| windows[label.as_str()].is_focused().unwrap_or(false));
+    let index = match focused {
+        Some(index) => (index as isize + step).rem_euclid(count),
+        None if step > 0 => 0,
+        None => count - 1,
+    };
+    let target = candidates[index as usize].clone();
+    order.streak = Some((labels, now));
+    drop(order);
+
+    let window = &windows[target.as_str()];
+    let _ = window.unminimize();
+    let _ = window.show();
+    window.set_focus().map_err(|e| e.to_string())?;
+    Ok(target)
+}
+
+/// `direction` is `next` or `prev`.
+#[tauri::command]
+pub async fn cycle_window(app: tauri::AppHandle, direction: String) -> Result<String, String> {
+    cycle(&app, &direction)
+}
+
+const ALWAYS_ON_TOP_KEY: &str = "always_on_top";
+
+fn always_on_top_windows(app: &tauri::AppHandle) -> HashMap<String, bool> {
+    settings::get(app, ALWAYS_ON_TOP_KEY).unwrap_or_default()
+}
+
+/// Re-applies the saved always-on-top choice to the windows that exist at startup.
+pub fn apply_always_on_top(app: &tauri::AppHandle) {
+    for (label, enabled) in always_on_top_windows(app) {
+        if let Some(window) = app.get_webview_window(&label) {
+            let _ = window.set_always_on_top(enabled);
+        }
+    }
+}
+
+/// Some Linux window managers ignore the hint, or refuse it outright; there the choice is
+/// still saved and the command succeeds.
+#[tauri::command]
+pub async fn set_always_on_top(app: tauri::AppHandle, window: tauri::Window, enabled: bool) -> Result<(), String> {
+    if let Err(e) = window.set_always_on_top(enabled) {
+        if cfg!(target_os = "linux") {
+            log::warn!("Window manager rejected always-on-top: {}", e);
+        } else {
+            return Err(e.to_string());
+        }
+    }
+
+    let mut windows = always_on_top_windows(&app);
+    if enabled {
+        windows.insert(window.label().to_string(), true);
+    } else {
+        windows.remove(window.label());
+    }
+    settings::set(&app, ALWAYS_ON_TOP_KEY, windows)
+}
+
+/// The user's choice for the calling window, which is also what applies where the window
+/// manager doesn't report the actual state.
+#[tauri::command]
+pub async fn get_always_on_top(app: tauri::AppHandle, window: tauri::Window) -> Result<bool, String> {
+    Ok(always_on_top_windows(&app).get(window.label()).copied().unwrap_or(false))
+}
+
+const OPACITY_KEY: &str = "window_opacity";
+/// Lower bound so the window can't be made invisible and lost.
+const MIN_OPACITY: f64 = 0.2;
+
+#[cfg(windows)]
+fn apply_opacity(window: &tauri::Window, opacity: f64) -> Result<(), String> {
+    use windows::Win32::Foundation::COLORREF;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetWindowLongPtrW, SetLayeredWindowAttributes, SetWindowLongPtrW, GWL_EXSTYLE, LWA_ALPHA, WS_EX_LAYERED,
+    };
+
+    let hwnd = window.hwnd().map_err(|e| e.to_string())?;
+    unsafe {
+        let style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE);
+        SetWindowLongPtrW(hwnd, GWL_EXSTYLE, style | WS_EX_LAYERED.0 as isize);
+        SetLayeredWindowAttributes(hwnd, COLORREF(0), (opacity * 255.0).round() as u8, LWA_ALPHA)
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn apply_opacity(window: &tauri::Window, opacity: f64) -> Result<(), String> {
+    let ns_window = window.ns_window().map_err(|e| e.to_string())? as usize;
+    window
+        .run_on_main_thread(move || {
+            let ns_window = unsafe { &*(ns_window as *const objc2_app_kit::NSWindow) };
+            ns_window.setAlphaValue(opacity);
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn apply_opacity(_window: &tauri::Window, _opacity: f64) -> Result<(), String> {
+    Err("opacity not supported on this platform".to_string())
+}
+
+/// Re-applies the last opacity to the main window at startup.
+pub fn restore_opacity(app: &tauri::AppHandle) {
+    let Some(opacity) = settings::get::<f64>(app, OPACITY_KEY) else { return };
+    if let Some(main_window) = app.get_webview_window("main") {
+        if let Err(e) = apply_opacity(&main_window.as_ref().window(), opacity.clamp(MIN_OPACITY, 1.0)) {
+            log::warn!("Failed to restore window opacity: {}", e);
+        }
+    }
+}
+
+/// `opacity` is clamped to 0.2–1.0, so the window can't become invisible.
+#[tauri::command]
+pub async fn set_window_opacity(app: tauri::AppHandle, window: tauri::Window, opacity: f64) -> Result<(), String> {
+    if !opacity.is_finite() {
+        return Err("invalid opacity".to_string());
+    }
+    let opacity = opacity.clamp(MIN_OPACITY, 1.0);
+    apply_opacity(&window, opacity)?;
+    settings::set(&app, OPACITY_KEY, opacity)
+}
+
+/// Flashes the taskbar entry (bounces the Dock icon once on macOS) to signal that something
+/// finished in the background. Does nothing if the window already has focus or "Nicht stören"
+/// is on.
+#[tauri::command]
+pub async fn flash_window(window: tauri::Window) -> Result<(), String> {
+    if dnd::is_enabled(window.app_handle()) || window.is_focused().map_err(|e| e.to_string())? {
+        return Ok(());
+    }
+    window
+        .request_user_attention(Some(tauri::UserAttentionType::Informational))
+        .map_err(|e| e.to_string())?;
+    window
+        .state::<AttentionRequests>()
+        .0
+        .lock()
+        .unwrap()
+        .insert(window.label().to_string());
+    Ok(())
+}
+
+/// Labels of the windows currently in zen mode.
+#[derive(Default)]
+pub struct ZenWindows(Mutex<HashSet<String>>);
+
+pub fn is_zen(app: &tauri::AppHandle, label: &str) -> bool {
+    app.state::<ZenWindows>().0.lock().unwrap().contains(label)
+}
+
+/// Zen mode hides the window decorations and the menu for distraction-free writing. On macOS
+/// the menu belongs to the app rather than the window and stays in place.
+#[tauri::command]
+pub async fn set_zen_mode(app: tauri::AppHandle, window: tauri::Window, enabled: bool) -> Result<(), String> {
+    let label = window.label().to_string();
+    let changed = {
+        let state = app.state::<ZenWindows>();
+        let mut zen = state.0.lock().unwrap();
+        if enabled {
+            zen.insert(label.clone())
+        } else {
+            zen.remove(&label)
+        }
+    };
+    if !changed {
+        return Ok(());
+    }
+
+    window.set_decorations(!enabled).map_err(|e| e.to_string())?;
+    if enabled {
+        window.hide_menu().map_err(|e| e.to_string())?;
+    } else if label == "main" && !cfg!(target_os = "macos") {
+        // Re-attaching replaces whatever menu the window has, so toggling never stacks menus.
+        if let Some(app_menu) = menu::current(&app) {
+            window.set_menu(app_menu).map_err(|e| e.to_string())?;
+        }
+    } else {
+        window.show_menu().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn main_window(app: &tauri::AppHandle) -> Result<tauri::WebviewWindow, String> {
+    app.get_webview_window("main")
+        .ok_or_else(|| "main window not found".to_string())
+}
+
+#[tauri::command]
+pub async fn minimize_window(app: tauri::AppHandle) -> Result<(), String> {
+    main_window(&app)?.minimize().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn maximize_window(app: tauri::AppHandle) -> Result<(), String> {
+    main_window(&app)?.maximize().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn unmaximize_window(app: tauri::AppHandle) -> Result<(), String> {
+    main_window(&app)?.unmaximize().map_err(|e| e.to_string())
+}
+
+/// Flips the maximized state. A minimized window comes back maximized, even if it was
+/// maximized before it was minimized (it would otherwise seem to do nothing).
+#[tauri::command]
+pub async fn toggle_maximize_window(app: tauri::AppHandle) -> Result<(), String> {
+    let window = main_window(&app)?;
+    if window.is_minimized().map_err(|e| e.to_string())? {
+        window.unminimize().map_err(|e| e.to_string())?;
+        return window.maximize().map_err(|e| e.to_string());
+    }
+    if window.is_maximized().map_err(|e| e.to_string())? {
+        window.unmaximize().map_err(|e| e.to_string())
+    } else {
+        window.maximize().map_err(|e| e.to_string())
+    }
+}
+
+/// For custom title bars: hands the pointer over to the OS to move the calling window. A
+/// call that arrives while a drag is already running fails with the platform's error, which
+/// the title bar can ignore.
+#[tauri::command]
+pub async fn start_window_drag(window: tauri::Window) -> Result<(), String> {
+    window.start_dragging().map_err(|e| e.to_string())
+}
+
+const MIN_BOUNDS: (u32, u32) = (400, 300);
+/// How much of the window must stay on a monitor to still be grabbed and moved back.
+const MIN_VISIBLE: i32 = 100;
+
+/// Outer position and inner size, in physical pixels.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct WindowBounds {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+/// Moves `bounds` onto the nearest monitor unless at least `MIN_VISIBLE` pixels of it are
+/// already on one. The title bar edge is kept below the monitor's top.
+fn clamp_to_monitors(bounds: WindowBounds, monitors: &[tauri::Monitor]) -> WindowBounds {
+    let rect = |monitor: &tauri::Monitor| {
+        let position = monitor.position();
+        let size = monitor.size();
+        (position.x, position.y, size.width as i32, size.height as i32)
+    };
+    let (width, height) = (bounds.width as i32, bounds.height as i32);
+    let visible = |(mx, my, mw, mh): (i32, i32, i32, i32)| {
+        let overlap_x = (bounds.x + width).min(mx + mw) - bounds.x.max(mx);
+        let overlap_y = (bounds.y + height).min(my + mh) - bounds.y.max(my);
+        overlap_x >= MIN_VISIBLE && overlap_y >= MIN_VISIBLE && bounds.y >= my
+    };
+    if monitors.iter().map(rect).any(visible) {
+        return bounds;
+    }
+
+    let distance = |(mx, my, mw, mh): &(i32, i32, i32, i32)| {
+        let dx = (bounds.x + width / 2 - (mx + mw / 2)) as i64;
+        let dy = (bounds.y + height / 2 - (my + mh / 2)) as i64;
+        dx * dx + dy * dy
+    };
+    let Some((mx, my, mw, mh)) = monitors.iter().map(rect).min_by_key(distance) else {
+        return bounds;
+    };
+    WindowBounds {
+        x: bounds.x.clamp(mx + MIN_VISIBLE - width, (mx + mw - MIN_VISIBLE).max(mx)),
+        y: bounds.y.clamp(my, (my + mh - MIN_VISIBLE).max(my)),
+        ..bounds
+    }
+}
+
+#[tauri::command]
+pub async fn get_window_bounds(window: tauri::Window) -> Result<WindowBounds, String> {
+    let position = window.outer_position().map_err(|e| e.to_string())?;
+    let size = window.inner_size().map_err(|e| e.to_string())?;
+    Ok(WindowBounds {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// Applies `bounds` to the calling window and returns them as applied: a position that would
+/// leave the window (nearly) off-screen is pulled back onto the closest monitor.
+#[tauri::command]
+pub async fn set_window_bounds(window: tauri::Window, bounds: WindowBounds) -> Result<WindowBounds, String> {
+    let (min_width, min_height) = MIN_BOUNDS;
+    if bounds.width < min_width || bounds.height < min_height {
+        return Err(format!(
+            "bounds_too_small: {}x{} is below the minimum of {}x{}",
+            bounds.width, bounds.height, min_width, min_height
+        ));
+    }
+
+    let monitors = window.available_monitors().map_err(|e| e.to_string())?;
+    let bounds = clamp_to_monitors(bounds, &monitors);
+    window
+        .set_size(tauri::PhysicalSize::new(bounds.width, bounds.height))
+        .map_err(|e| e.to_string())?;
+    window
+        .set_position(tauri::PhysicalPosition::new(bounds.x, bounds.y))
+        .map_err(|e| e.to_string())?;
+    Ok(bounds)
+}
+
+/// Centers the calling window on the monitor it overlaps most, or on the primary monitor when
+/// that can't be resolved (e.g. the window is entirely off-screen). Emits `window-centered`.
+#[tauri::command]
+pub async fn center_window(window: tauri::Window) -> Result<(), String> {
+    let current = window.current_monitor().map_err(|e| e.to_string())?;
+    let primary = window.primary_monitor().map_err(|e| e.to_string())?;
+    if let (None, Some(primary)) = (current, primary) {
+        let size = window.outer_size().map_err(|e| e.to_string())?;
+        let area = primary.work_area();
+        let x = area.position.x + (area.size.width as i32 - size.width as i32) / 2;
+        let y = area.position.y + (area.size.height as i32 - size.height as i32) / 2;
+        window
+            .set_position(tauri::PhysicalPosition::new(x, y.max(area.position.y)))
+            .map_err(|e| e.to_string())?;
+    } else {
+        window.center().map_err(|e| e.to_string())?;
+    }
+    let _ = window.emit("window-centered", window.label());
+    Ok(())
+}
+
+const MAX_TITLE_CHARS: usize = 255;
+
+pub(crate) fn clean_title(title: &str) -> Result<String, String> {
+    let title: String = title.chars().filter(|c| !c.is_control()).collect();
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("empty_title".to_string());
+    }
+    if title.chars().count() >= MAX_TITLE_CHARS {
+        return Err(format!("title_too_long: at most {} characters", MAX_TITLE_CHARS - 1));
+    }
+    Ok(title.to_string())
+}
+
+/// Sets the calling window's title as given, e.g. `Pressemitteilung – Grünerator`; control
+/// characters are dropped.
+#[tauri::command]
+pub async fn set_window_title(window: tauri::Window, title: String) -> Result<(), String> {
+    window.set_title(&clean_title(&title)?).map_err(|e| e.to_string())
+}
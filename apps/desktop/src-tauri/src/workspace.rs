@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::settings;
+
+const LAYOUTS_KEY: &str = "layouts";
+const DEFAULT_PROFILE: &str = "default";
+const MAX_LAYOUT_BYTES: usize = 64 * 1024;
+
+#[derive(Serialize)]
+pub struct StoredLayout {
+    layout: Option<Value>,
+    is_default: bool,
+}
+
+#[tauri::command]
+pub async fn save_layout(
+    app: tauri::AppHandle,
+    json: String,
+    profile: Option<String>,
+) -> Result<(), String> {
+    if json.len() > MAX_LAYOUT_BYTES {
+        return Err("layout too large".to_string());
+    }
+    let layout: Value = serde_json::from_str(&json).map_err(|e| format!("invalid layout JSON: {}", e))?;
+
+    let mut layouts: HashMap<String, Value> = settings::get(&app, LAYOUTS_KEY).unwrap_or_default();
+    layouts.insert(profile.unwrap_or_else(|| DEFAULT_PROFILE.to_string()), layout);
+    settings::set(&app, LAYOUTS_KEY, layouts)
+}
+
+#[tauri::command]
+pub async fn load_layout(app: tauri::AppHandle, profile: Option<String>) -> Result<StoredLayout, String> {
+    let mut layouts: HashMap<String, Value> = settings::get(&app, LAYOUTS_KEY).unwrap_or_default();
+    let layout = layouts.remove(profile.as_deref().unwrap_or(DEFAULT_PROFILE));
+    Ok(StoredLayout {
+        is_default: layout.is_none(),
+        layout,
+    })
+}
@@ -13,6 +13,7 @@ import ScrollToTop from './components/utils/ScrollToTop';
 import { routes } from './config/routes';
 import { useFirstRun } from './features/desktop/hooks/useFirstRun';
 import { useAuthStore } from './stores/authStore';
+import { runHeadlessGeneration } from './utils/desktopHeadless';
 import './App.css';
 
 // Lazy-load FirstRunWizard (desktop-only component)
@@ -83,6 +84,8 @@ function App() {
     initializeApiClient()
       .then(() => {
         setAppReady(true);
+        // Headless desktop launches (`--headless`) pull their prompt once we can call the API
+        void runHeadlessGeneration();
       })
       .catch((error) => {
         console.error('[App] Initialization failed:', error);
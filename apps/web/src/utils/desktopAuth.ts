@@ -5,10 +5,11 @@
  * Tokens are stored securely using Tauri's store plugin.
  *
  * Flow:
- * 1. User clicks login → opens system browser to /auth/login
+ * 1. User clicks login → registers a random state with Tauri, opens system browser to /auth/login
  * 2. User authenticates with Keycloak
- * 3. Callback redirects to gruenerator://auth/callback?code=<jwt>
- * 4. Tauri receives deep-link, emits event
+ * 3. Callback redirects to <scheme>://auth/callback?state=<state>&code=<jwt>
+ *    (gruenerator://, or gruenerator-staging:// for staging builds)
+ * 4. Tauri receives deep-link, checks the state, emits event
  * 5. We exchange the code for access + refresh tokens
  * 6. Tokens stored in secure storage
  */
@@ -26,7 +27,6 @@ const getSecureStorage = async () => {
 };
 
 const API_BASE_URL = import.meta.env.VITE_API_BASE_URL || '/api';
-const REDIRECT_URI = 'gruenerator://auth/callback';
 
 export type AuthSource =
   | 'gruenerator-login'
@@ -48,6 +48,13 @@ export interface DesktopUser {
   [key: string]: unknown;
 }
 
+interface AuthCallback {
+  code: string | null;
+  state: string;
+  error: string | null;
+  error_description: string | null;
+}
+
 interface TokenResponse {
   success: boolean;
   access_token?: string;
@@ -74,17 +81,13 @@ export async function initDesktopAuth(
   try {
     const { listen } = await import('@tauri-apps/api/event');
 
-    const unlisten = await listen<string>('deep-link-auth', async (event) => {
-      const url = event.payload;
-      console.log('[DesktopAuth] Received deep-link:', url);
+    const unlistenAuth = await listen<AuthCallback>('deep-link-auth', async (event) => {
+      const { code, error, error_description } = event.payload;
+      console.log('[DesktopAuth] Received auth callback');
 
       try {
-        const parsedUrl = new URL(url);
-        const code = parsedUrl.searchParams.get('code');
-        const error = parsedUrl.searchParams.get('error');
-
         if (error) {
-          onAuthError(error);
+          onAuthError(error_description || error);
           return;
         }
 
@@ -107,11 +110,24 @@ export async function initDesktopAuth(
       }
     });
 
-    deepLinkCleanup = unlisten;
+    // Callbacks the app rejected (missing_state, state_mismatch, state_expired, invalid_callback)
+    const unlistenError = await listen<{ reason: string }>('deep-link-auth-error', (event) => {
+      console.warn('[DesktopAuth] Auth callback rejected:', event.payload.reason);
+      onAuthError(event.payload.reason);
+    });
+
+    deepLinkCleanup = () => {
+      unlistenAuth();
+      unlistenError();
+    };
     console.log('[DesktopAuth] Deep-link listener initialized');
 
     // Check for existing auth on startup
     await restoreSession(onAuthSuccess);
+
+    // The app holds back callbacks (e.g. from a login link that launched it) until we listen
+    const { invoke } = await import('@tauri-apps/api/core');
+    await invoke('frontend_ready');
   } catch (error) {
     console.error('[DesktopAuth] Failed to initialize:', error);
   }
@@ -190,7 +206,15 @@ export async function openDesktopLogin(source: AuthSource = 'gruenerator-login')
 
   try {
     const { open } = await import('@tauri-apps/plugin-shell');
-    const authUrl = `${API_BASE_URL}/auth/login?source=${source}&redirectTo=${encodeURIComponent(REDIRECT_URI)}`;
+    const { invoke } = await import('@tauri-apps/api/core');
+
+    // The API keeps the redirect's query and appends the code, so the state comes back with it
+    const state = crypto.randomUUID();
+    await invoke('set_oauth_state', { state });
+    // Staging builds register their own scheme; a production callback would open the wrong app
+    const scheme = await invoke<string>('get_deep_link_scheme');
+    const redirectUri = `${scheme}://auth/callback?state=${encodeURIComponent(state)}`;
+    const authUrl = `${API_BASE_URL}/auth/login?source=${source}&redirectTo=${encodeURIComponent(redirectUri)}`;
     console.log('[DesktopAuth] Opening browser:', authUrl);
     await open(authUrl);
   } catch (error) {
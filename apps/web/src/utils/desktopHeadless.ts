@@ -0,0 +1,58 @@
+/**
+ * Desktop (Tauri) headless generation
+ *
+ * `Grünerator --headless --prompt "…" --out rede.md` loads the app without showing a window.
+ * Once the app has mounted we ask Tauri for the prompt, generate the text with the stored
+ * session and hand the result back; Tauri writes it to the output file and exits.
+ */
+
+import { isDesktopApp } from './platform';
+
+import type { AxiosRequestConfig } from 'axios';
+
+// Extend axios config to allow skipAuthRedirect
+interface ExtendedAxiosRequestConfig extends AxiosRequestConfig {
+  skipAuthRedirect?: boolean;
+}
+
+interface HeadlessRequest {
+  prompt: string;
+}
+
+/**
+ * Runs the pending headless generation, if this is a headless launch (call once the API
+ * client is initialized). Does nothing for regular launches.
+ */
+export async function runHeadlessGeneration(): Promise<void> {
+  if (!isDesktopApp()) return;
+
+  const { invoke } = await import('@tauri-apps/api/core');
+  let request: HeadlessRequest | null;
+  try {
+    request = await invoke<HeadlessRequest | null>('get_headless_request');
+  } catch (error) {
+    console.error('[DesktopHeadless] Failed to read headless request:', error);
+    return;
+  }
+  if (!request) return;
+
+  try {
+    const { default: apiClient } = await import('../components/utils/apiClient');
+    // A 401 must fail the run instead of navigating to the login page nobody can see
+    const config: ExtendedAxiosRequestConfig = { skipAuthRedirect: true };
+    const response = await apiClient.post('/texte/smart', { inhalt: request.prompt }, config);
+    const data = response.data as unknown;
+    const text =
+      typeof data === 'string' ? data : (data as Record<string, unknown> | null)?.content;
+
+    if (typeof text !== 'string' || !text.trim()) {
+      throw new Error('Empty response received from API');
+    }
+    await invoke('submit_headless_result', { text });
+  } catch (error) {
+    console.error('[DesktopHeadless] Generation failed:', error);
+    await invoke('submit_headless_result', {
+      error: error instanceof Error ? error.message : String(error),
+    });
+  }
+}